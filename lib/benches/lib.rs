@@ -109,6 +109,12 @@ fn base64_decode_base(b: &mut Bencher) {
     b.iter(|| data_encoding::BASE64_NOPAD.decode_mut(input, output));
 }
 
+#[bench]
+fn base64_validate_base(b: &mut Bencher) {
+    let input = &[b'A'; 4096];
+    b.iter(|| data_encoding::BASE64_NOPAD.validate(input));
+}
+
 #[bench]
 fn base64_encode_pad(b: &mut Bencher) {
     let input = &mut [b'A'; 4096];
@@ -156,6 +162,20 @@ fn base64_decode_wrap(b: &mut Bencher) {
     b.iter(|| base64.decode_mut(input, output).unwrap());
 }
 
+#[bench]
+fn base64_validate_wrap(b: &mut Bencher) {
+    let input = &mut [b'A'; 4096];
+    for i in 0 .. 20 {
+        let x = 4096 * i / 20 / 4 * 4;
+        input[x + 3] = b'\n';
+    }
+    let mut spec = data_encoding::BASE64.specification();
+    spec.wrap.width = 76;
+    spec.wrap.separator.push_str("\r\n");
+    let base64 = spec.encoding().unwrap();
+    b.iter(|| base64.validate(input).unwrap());
+}
+
 #[bench]
 fn dnscurve_decode_base(b: &mut Bencher) {
     let input = &[b'0'; 4096];
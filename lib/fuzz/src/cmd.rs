@@ -37,8 +37,16 @@ pub fn execute(target: &str, mut input: &[u8]) -> Output {
             let Ok(base) = spec.encoding() else { return output.reject() };
             let spec = base.specification();
             stat_spec(&mut output, &spec, &base);
-            let input = gen::rev_spec(&spec);
-            assert_eq!(gen::spec(&mut input.as_slice()).encoding().unwrap(), base);
+            let data = gen::rev_spec(&spec);
+            assert_eq!(gen::spec(&mut data.as_slice()).encoding().unwrap(), base);
+            let prefix = gen::any_prefix(&mut input).unwrap_or_default();
+            if let Ok(prefixed) = base.with_wrap_prefix(&prefix) {
+                output.insert("prefix_len", prefix.len());
+                let spec = prefixed.specification();
+                assert_eq!(spec.encoding().unwrap().with_wrap_prefix(&prefix).unwrap(), prefixed);
+                let encoded = prefixed.encode(input);
+                assert_eq!(prefixed.decode(encoded.as_bytes()).unwrap(), input);
+            }
         }
         "impl_encode_len" => {
             let (_, base) = gen_spec_base(&mut input, &mut output);
@@ -115,6 +115,10 @@ pub fn any_spec(data: &mut &[u8]) -> Option<Specification> {
     })
 }
 
+pub fn any_prefix(data: &mut &[u8]) -> Option<String> {
+    string(data)
+}
+
 pub fn bytes<'a>(data: &'_ mut &'a [u8], len: usize) -> &'a [u8] {
     let len = std::cmp::min(len, data.len());
     let res = &data[.. len];
@@ -124,7 +124,8 @@ fn get_encoding(hash_map: &mut HashMap<String, TokenTree>) -> Encoding {
             to: get_string(hash_map, "translate_to"),
         },
     };
-    spec.encoding().unwrap()
+    let prefix = get_string(hash_map, "wrap_prefix");
+    spec.encoding().unwrap().with_wrap_prefix(&prefix).unwrap()
 }
 
 fn check_empty<T>(hash_map: HashMap<String, T>) {
@@ -131,6 +131,7 @@ macro_rules! decode_slice {
 ///              ignore: [""]|<string>,  // e.g. " \t\n"
 ///          wrap_width: [0]|<int>,      // e.g. 76
 ///      wrap_separator: [""]|<string>,  // e.g. "\r\n"
+///         wrap_prefix: [""]|<string>,  // e.g. "> "
 ///      translate_from: [""]|<string>,  // e.g. "ABCDEF"
 ///        translate_to: [""]|<string>,  // e.g. "abcdef"
 /// ```
@@ -53,6 +53,18 @@ fn decode_slice() {
     assert_eq!(OUTPUT, BASE.decode(b"deadbeef").unwrap());
 }
 
+#[test]
+fn wrap_prefix() {
+    const BASE: data_encoding::Encoding = data_encoding_macro::new_encoding! {
+        symbols: "0123456789abcdef",
+        wrap_width: 4,
+        wrap_separator: "\n",
+        wrap_prefix: "> ",
+    };
+    assert_eq!(BASE.encode(b"\xde\xad\xbe\xef"), "> dead\n> beef\n");
+    assert_eq!(BASE.decode(b"> dead\n> beef\n").unwrap(), b"\xde\xad\xbe\xef");
+}
+
 #[test]
 fn hexlower_decode_array() {
     data_encoding_macro::hexlower_array!("const OUTPUT" = "deadbeef");
@@ -0,0 +1,234 @@
+//! Base45 encoding
+//!
+//! Base45 encodes blocks of 2 bytes as blocks of 3 characters by writing the big-endian 16-bits
+//! value of the block in base 45 with the least significant digit first. A last single byte is
+//! encoded as 2 characters. It is defined by [RFC9285] and used by QR codes (e.g. EU Digital COVID
+//! Certificates), because its alphabet is a subset of the QR code alphanumeric mode.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::base45::BASE45;
+//! assert_eq!(BASE45.encode(b"Hello!!"), "%69 VD92EX0");
+//! assert_eq!(BASE45.decode(b"%69 VD92EX0").unwrap(), b"Hello!!");
+//! ```
+//!
+//! [RFC9285]: https://tools.ietf.org/html/rfc9285
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{DecodeError, DecodeKind, DecodePartial};
+
+/// Base45 encoding
+///
+/// See the [module] documentation for more details.
+///
+/// [module]: index.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base45 {
+    symbols: [u8; 45],
+    values: [u8; 256],
+}
+
+const INVALID: u8 = 128;
+
+const fn new(symbols: &[u8; 45]) -> Base45 {
+    let mut values = [INVALID; 256];
+    let mut i = 0;
+    while i < 45 {
+        values[symbols[i] as usize] = i as u8;
+        i += 1;
+    }
+    Base45 { symbols: *symbols, values }
+}
+
+/// Base45 encoding
+///
+/// This encoding uses the `0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:` alphabet (including the
+/// space character). It conforms to [RFC9285].
+///
+/// [RFC9285]: https://tools.ietf.org/html/rfc9285
+pub const BASE45: Base45 = new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:");
+
+impl Base45 {
+    /// Returns the encoded length of an input of length `len`
+    ///
+    /// See [`encode_mut`] for when to use it.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `len` is greater than `usize::MAX / 2`.
+    ///
+    /// [`encode_mut`]: struct.Base45.html#method.encode_mut
+    #[must_use]
+    pub fn encode_len(&self, len: usize) -> usize {
+        len / 2 * 3 + len % 2 * 2
+    }
+
+    /// Encodes `input` in `output`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of [`encode_len`] for the `input`
+    /// length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base45::BASE45;
+    /// # let mut buffer = vec![0; 100];
+    /// let input = b"ietf!";
+    /// let output = &mut buffer[0 .. BASE45.encode_len(input.len())];
+    /// BASE45.encode_mut(input, output);
+    /// assert_eq!(output, b"QED8WEX0");
+    /// ```
+    ///
+    /// [`encode_len`]: struct.Base45.html#method.encode_len
+    pub fn encode_mut(&self, input: &[u8], output: &mut [u8]) {
+        assert_eq!(output.len(), self.encode_len(input.len()));
+        for (input, output) in input.chunks(2).zip(output.chunks_mut(3)) {
+            let mut x = input.iter().fold(0, |x, &y| x << 8 | usize::from(y));
+            for output in output {
+                *output = self.symbols[x % 45];
+                x /= 45;
+            }
+        }
+    }
+
+    /// Returns encoded `input`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base45::BASE45;
+    /// assert_eq!(BASE45.encode(b"AB"), "BB8");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode(&self, input: &[u8]) -> String {
+        let mut output = vec![0u8; self.encode_len(input.len())];
+        self.encode_mut(input, &mut output);
+        crate::safety_assert!(output.is_ascii());
+        // SAFETY: Ensured by correctness guarantees of encode_mut (and asserted above).
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+
+    /// Returns the decoded length of an input of length `len`
+    ///
+    /// See [`decode_mut`] for when to use it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `len` is invalid. The error kind is [`Length`] and the [position] is the
+    /// greatest valid input length.
+    ///
+    /// [`Length`]: ../enum.DecodeKind.html#variant.Length
+    /// [`decode_mut`]: struct.Base45.html#method.decode_mut
+    /// [position]: ../struct.DecodeError.html#structfield.position
+    pub fn decode_len(&self, len: usize) -> Result<usize, DecodeError> {
+        if len % 3 == 1 {
+            return Err(DecodeError { position: len - 1, kind: DecodeKind::Length });
+        }
+        Ok(len / 3 * 2 + len % 3 / 2)
+    }
+
+    /// Decodes `input` in `output`
+    ///
+    /// Returns the length of the decoded output, which is always the output length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of [`decode_len`] for the `input`
+    /// length. Also panics if `decode_len` fails for the `input` length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. See [`decode`] for more details. The [`read`] first
+    /// bytes of the input have been successfully decoded to the [`written`] first bytes of the
+    /// output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base45::BASE45;
+    /// # let mut buffer = vec![0; 100];
+    /// let input = b"QED8WEX0";
+    /// let output = &mut buffer[0 .. BASE45.decode_len(input.len()).unwrap()];
+    /// let len = BASE45.decode_mut(input, output).unwrap();
+    /// assert_eq!(&output[0 .. len], b"ietf!");
+    /// ```
+    ///
+    /// [`decode_len`]: struct.Base45.html#method.decode_len
+    /// [`decode`]: struct.Base45.html#method.decode
+    /// [`read`]: ../struct.DecodePartial.html#structfield.read
+    /// [`written`]: ../struct.DecodePartial.html#structfield.written
+    pub fn decode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, DecodePartial> {
+        assert_eq!(Ok(output.len()), self.decode_len(input.len()));
+        for (i, (input, output)) in input.chunks(3).zip(output.chunks_mut(2)).enumerate() {
+            let fail = |j, kind| DecodePartial {
+                read: 3 * i,
+                written: 2 * i,
+                error: DecodeError { position: 3 * i + j, kind },
+            };
+            let mut x = 0;
+            for (j, &y) in input.iter().enumerate() {
+                let y = self.values[y as usize];
+                if y == INVALID {
+                    return Err(fail(j, DecodeKind::Symbol));
+                }
+                x += usize::from(y) * [1, 45, 45 * 45][j];
+            }
+            if x >> (8 * output.len()) != 0 {
+                return Err(fail(input.len() - 1, DecodeKind::Symbol));
+            }
+            let n = output.len();
+            for (j, output) in output.iter_mut().enumerate() {
+                *output = (x >> (8 * (n - 1 - j))) as u8;
+            }
+        }
+        Ok(output.len())
+    }
+
+    /// Returns decoded `input`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. The error kind can be:
+    ///
+    /// - [`Length`] if the input length is 1 modulo 3. The position is the greatest valid input
+    ///   length.
+    /// - [`Symbol`] if the input contains a character outside the alphabet. The position is the
+    ///   one of this character.
+    /// - [`Symbol`] if a block encodes a value that does not fit its decoded length (65535 for 3
+    ///   characters and 255 for 2 characters). The position is the one of the last character of
+    ///   the block, which holds the most significant digit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::{DecodeError, DecodeKind};
+    /// use data_encoding::base45::BASE45;
+    /// assert_eq!(BASE45.decode(b"QED8WEX0").unwrap(), b"ietf!");
+    /// assert_eq!(BASE45.decode(b"QED8W").unwrap_err().position, 4);
+    /// assert_eq!(BASE45.decode(b"QEd").unwrap_err().kind, DecodeKind::Symbol);
+    /// assert_eq!(BASE45.decode(b"GGW").unwrap_err(), DecodeError {
+    ///     position: 2,
+    ///     kind: DecodeKind::Symbol
+    /// });
+    /// ```
+    ///
+    /// [`Length`]: ../enum.DecodeKind.html#variant.Length
+    /// [`Symbol`]: ../enum.DecodeKind.html#variant.Symbol
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut output = vec![0u8; self.decode_len(input.len())?];
+        let len = self.decode_mut(input, &mut output).map_err(|partial| partial.error)?;
+        output.truncate(len);
+        Ok(output)
+    }
+}
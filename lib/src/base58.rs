@@ -0,0 +1,403 @@
+//! Base58 encodings
+//!
+//! Base58 encodings interpret their input as a big-endian number and write it in base 58. Leading
+//! zero bytes are encoded as leading zero symbols (the first symbol of the alphabet), such that the
+//! encoding is bijective. This is unlike the [`Encoding`] type, which only
+//! supports bases of size a power of 2.
+//!
+//! Because the whole input is a single number, encoding and decoding take quadratic time in the
+//! input length. This is fine for the usual inputs (like addresses or hashes), but base58 should
+//! not be used for long inputs.
+//!
+//! This module provides the [`BITCOIN`], [`RIPPLE`], and [`FLICKR`] alphabets.
+//! It also provides [Base58Check], which appends a checksum to the input.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::base58::BITCOIN;
+//! assert_eq!(BITCOIN.encode(b"Hello world"), "JxF12TrwXzT5jvT");
+//! assert_eq!(BITCOIN.decode(b"JxF12TrwXzT5jvT").unwrap(), b"Hello world");
+//! ```
+//!
+//! [`Encoding`]: ../struct.Encoding.html
+//! [`BITCOIN`]: constant.BITCOIN.html
+//! [`RIPPLE`]: constant.RIPPLE.html
+//! [`FLICKR`]: constant.FLICKR.html
+//! [Base58Check]: struct.Base58.html#method.encode_check
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{DecodeError, DecodeKind, DecodePartial};
+
+/// Base58 encoding
+///
+/// See the [module] documentation for more details.
+///
+/// [module]: index.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base58 {
+    symbols: [u8; 58],
+    values: [u8; 256],
+}
+
+const INVALID: u8 = 128;
+
+const fn new(symbols: &[u8; 58]) -> Base58 {
+    let mut values = [INVALID; 256];
+    let mut i = 0;
+    while i < 58 {
+        values[symbols[i] as usize] = i as u8;
+        i += 1;
+    }
+    Base58 { symbols: *symbols, values }
+}
+
+/// Bitcoin base58 encoding
+///
+/// This encoding uses the `123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz` alphabet.
+/// It is also used by IPFS (e.g. CIDv0), Monero, and Solana.
+pub const BITCOIN: Base58 = new(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+/// Ripple base58 encoding
+///
+/// This encoding uses the `rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz` alphabet.
+pub const RIPPLE: Base58 = new(b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+
+/// Flickr base58 encoding
+///
+/// This encoding uses the `123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ` alphabet.
+pub const FLICKR: Base58 = new(b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ");
+
+impl Base58 {
+    /// Returns the maximum encoded length of an input of length `len`
+    ///
+    /// See [`encode_mut`] for when to use it. The actual encoded length
+    /// depends on the input value.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `len` is greater than `usize::MAX / 138`.
+    ///
+    /// [`encode_mut`]: struct.Base58.html#method.encode_mut
+    #[must_use]
+    pub fn encode_len(&self, len: usize) -> usize {
+        // The ratio is log(256) / log(58) < 1.38.
+        len * 138 / 100 + 1
+    }
+
+    /// Encodes `input` in `output`
+    ///
+    /// Returns the length of the encoded output. The output bytes after the returned length are
+    /// not meaningful and should not be read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of
+    /// [`encode_len`] for the `input` length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base58::BITCOIN;
+    /// # let mut buffer = vec![0; 100];
+    /// let input = b"Hello world";
+    /// let output = &mut buffer[0 .. BITCOIN.encode_len(input.len())];
+    /// let len = BITCOIN.encode_mut(input, output);
+    /// assert_eq!(&output[0 .. len], b"JxF12TrwXzT5jvT");
+    /// ```
+    ///
+    /// [`encode_len`]: struct.Base58.html#method.encode_len
+    pub fn encode_mut(&self, input: &[u8], output: &mut [u8]) -> usize {
+        assert_eq!(output.len(), self.encode_len(input.len()));
+        let zeros = input.iter().take_while(|&&x| x == 0).count();
+        // We first compute the base58 digits in little-endian order.
+        let mut len = 0;
+        for &x in &input[zeros ..] {
+            let mut carry = u32::from(x);
+            for digit in &mut output[.. len] {
+                carry += u32::from(*digit) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                output[len] = (carry % 58) as u8;
+                len += 1;
+                carry /= 58;
+            }
+        }
+        // We then reverse them and make room for the leading zeros.
+        output[.. len].reverse();
+        output.copy_within(.. len, zeros);
+        for x in &mut output[.. zeros] {
+            *x = 0;
+        }
+        for x in &mut output[.. zeros + len] {
+            *x = self.symbols[*x as usize];
+        }
+        zeros + len
+    }
+
+    /// Returns encoded `input`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base58::BITCOIN;
+    /// assert_eq!(BITCOIN.encode(b"\0\0Hello world"), "11JxF12TrwXzT5jvT");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode(&self, input: &[u8]) -> String {
+        let mut output = vec![0u8; self.encode_len(input.len())];
+        let len = self.encode_mut(input, &mut output);
+        output.truncate(len);
+        crate::safety_assert!(output.is_ascii());
+        // SAFETY: Ensured by correctness guarantees of encode_mut (and asserted above).
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+
+    /// Returns the maximum decoded length of an input of length `len`
+    ///
+    /// See [`decode_mut`] for when to use it. The actual decoded length
+    /// depends on the input value.
+    ///
+    /// [`decode_mut`]: struct.Base58.html#method.decode_mut
+    #[must_use]
+    pub fn decode_len(&self, len: usize) -> usize {
+        len
+    }
+
+    /// Decodes `input` in `output`
+    ///
+    /// Returns the length of the decoded output. The output bytes after the returned length are
+    /// not meaningful and should not be read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of
+    /// [`decode_len`] for the `input` length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. See [`decode`] for more details.
+    /// Because the input is decoded as a whole, the [`read`] and [`written`] fields of the error
+    /// are always zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base58::BITCOIN;
+    /// # let mut buffer = vec![0; 100];
+    /// let input = b"JxF12TrwXzT5jvT";
+    /// let output = &mut buffer[0 .. BITCOIN.decode_len(input.len())];
+    /// let len = BITCOIN.decode_mut(input, output).unwrap();
+    /// assert_eq!(&output[0 .. len], b"Hello world");
+    /// ```
+    ///
+    /// [`read`]: ../struct.DecodePartial.html#structfield.read
+    /// [`decode_len`]: struct.Base58.html#method.decode_len
+    /// [`decode`]: struct.Base58.html#method.decode
+    /// [`written`]: ../struct.DecodePartial.html#structfield.written
+    pub fn decode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, DecodePartial> {
+        assert_eq!(output.len(), self.decode_len(input.len()));
+        let zeros = input.iter().take_while(|&&x| x == self.symbols[0]).count();
+        // We first compute the bytes in little-endian order.
+        let mut len = 0;
+        for (i, &x) in input.iter().enumerate().skip(zeros) {
+            let value = self.values[x as usize];
+            if value == INVALID {
+                let error = DecodeError { position: i, kind: DecodeKind::Symbol };
+                return Err(DecodePartial { read: 0, written: 0, error });
+            }
+            let mut carry = u32::from(value);
+            for byte in &mut output[.. len] {
+                carry += u32::from(*byte) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                output[len] = carry as u8;
+                len += 1;
+                carry >>= 8;
+            }
+        }
+        // We then reverse them and add the leading zeros.
+        output[.. len].reverse();
+        output.copy_within(.. len, zeros);
+        for x in &mut output[.. zeros] {
+            *x = 0;
+        }
+        Ok(zeros + len)
+    }
+
+    /// Returns decoded `input`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. The error kind is always [`Symbol`] and the
+    /// [position] is the first invalid character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base58::BITCOIN;
+    /// assert_eq!(BITCOIN.decode(b"11JxF12TrwXzT5jvT").unwrap(), b"\0\0Hello world");
+    /// ```
+    ///
+    /// [`Symbol`]: ../enum.DecodeKind.html#variant.Symbol
+    /// [position]: ../struct.DecodeError.html#structfield.position
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut output = vec![0u8; self.decode_len(input.len())];
+        let len = self.decode_mut(input, &mut output).map_err(|partial| partial.error)?;
+        output.truncate(len);
+        Ok(output)
+    }
+
+    /// Returns encoded `input` with a Base58Check checksum
+    ///
+    /// The checksum is the first 4 bytes of the double SHA-256 of the input. It is appended to
+    /// the input before encoding. The input usually starts with a version byte, which is not
+    /// treated specially.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base58::BITCOIN;
+    /// let mut input = vec![0];
+    /// input.extend_from_slice(&[0x01, 0x09, 0x66, 0x77, 0x60, 0x06, 0x95, 0x3d, 0x55, 0x67]);
+    /// input.extend_from_slice(&[0x43, 0x9e, 0x5e, 0x39, 0xf8, 0x6a, 0x0d, 0x27, 0x3b, 0xee]);
+    /// assert_eq!(BITCOIN.encode_check(&input), "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode_check(&self, input: &[u8]) -> String {
+        let mut payload = input.to_vec();
+        payload.extend_from_slice(&checksum(input));
+        self.encode(&payload)
+    }
+
+    /// Returns decoded `input` after checking and removing its Base58Check checksum
+    ///
+    /// See [`encode_check`] for more details.
+    ///
+    /// [`encode_check`]: struct.Base58.html#method.encode_check
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. The error kind can be:
+    ///
+    /// - [`Symbol`] if the input contains an invalid character. The [position] is the first
+    ///   invalid character.
+    /// - [`Length`] if the decoded input is shorter than the checksum. The [position] is the input
+    ///   length.
+    /// - [`Trailing`] if the checksum (the trailing 4 bytes of the decoded input) is invalid. The
+    ///   [position] is the last character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base58::BITCOIN;
+    /// use data_encoding::{DecodeError, DecodeKind};
+    /// let output = BITCOIN.decode_check(b"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM").unwrap();
+    /// assert_eq!(output.len(), 21);
+    /// assert_eq!(
+    ///     BITCOIN.decode_check(b"16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"),
+    ///     Err(DecodeError { position: 32, kind: DecodeKind::Trailing })
+    /// );
+    /// ```
+    ///
+    /// [`Length`]: ../enum.DecodeKind.html#variant.Length
+    /// [`Symbol`]: ../enum.DecodeKind.html#variant.Symbol
+    /// [`Trailing`]: ../enum.DecodeKind.html#variant.Trailing
+    /// [position]: ../struct.DecodeError.html#structfield.position
+    #[cfg(feature = "alloc")]
+    pub fn decode_check(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut output = self.decode(input)?;
+        let len = match output.len().checked_sub(4) {
+            Some(len) => len,
+            None => return Err(DecodeError { position: input.len(), kind: DecodeKind::Length }),
+        };
+        if output[len ..] != checksum(&output[.. len]) {
+            return Err(DecodeError { position: input.len() - 1, kind: DecodeKind::Trailing });
+        }
+        output.truncate(len);
+        Ok(output)
+    }
+}
+
+// Returns the Base58Check checksum of `input`.
+#[cfg(feature = "alloc")]
+fn checksum(input: &[u8]) -> [u8; 4] {
+    let hash = sha256(&sha256(input));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// Returns the SHA-256 of `input` (FIPS 180-4).
+#[cfg(feature = "alloc")]
+fn sha256(input: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    let mut message = input.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(input.len() as u64 * 8).to_be_bytes());
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16 .. 64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ w[i - 15] >> 3;
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ w[i - 2] >> 10;
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0 .. 64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (x, y) in state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+            *x = x.wrapping_add(*y);
+        }
+    }
+    let mut output = [0u8; 32];
+    for (output, x) in output.chunks_mut(4).zip(state.iter()) {
+        output.copy_from_slice(&x.to_be_bytes());
+    }
+    output
+}
@@ -0,0 +1,424 @@
+//! Base85 encodings
+//!
+//! Base85 encodings encode blocks of 4 bytes as blocks of 5 characters by writing the big-endian
+//! 32-bits value of the block in base 85. The last block may be partial: a block of 1 to 3 bytes
+//! is padded with zeros, encoded, and only its first 2 to 4 characters are kept. This is unlike the
+//! [`Encoding`] type, which only supports bases of size a power of 2.
+//!
+//! This module provides the following encodings:
+//!
+//! - [`ASCII85`] for PostScript and PDF streams (Adobe variant with `<~` and `~>` delimiters and
+//!   the `z` shortcut)
+//! - [`Z85`] for ZeroMQ keys
+//! - [`RFC1924`] for the character set of [RFC1924] (as used by Git and Python)
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::base85::{ASCII85, Z85};
+//! assert_eq!(ASCII85.encode(b"Hello world"), "<~87cURD]j7BEbo7~>");
+//! assert_eq!(Z85.decode(b"HelloWorld").unwrap(), [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]);
+//! ```
+//!
+//! [`ASCII85`]: constant.ASCII85.html
+//! [`Encoding`]: ../struct.Encoding.html
+//! [`RFC1924`]: constant.RFC1924.html
+//! [`Z85`]: constant.Z85.html
+//! [RFC1924]: https://tools.ietf.org/html/rfc1924
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{DecodeError, DecodeKind, DecodePartial};
+
+/// Base85 encoding
+///
+/// See the [module] documentation for more details.
+///
+/// [module]: index.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base85 {
+    symbols: [u8; 85],
+    values: [u8; 256],
+    ascii85: bool,
+}
+
+const INVALID: u8 = 128;
+const IGNORE: u8 = 129;
+
+const fn new(symbols: &[u8; 85], ascii85: bool) -> Base85 {
+    let mut values = [INVALID; 256];
+    let mut i = 0;
+    while i < 85 {
+        values[symbols[i] as usize] = i as u8;
+        i += 1;
+    }
+    if ascii85 {
+        let ignore = b"\0\t\n\x0c\r ";
+        let mut i = 0;
+        while i < ignore.len() {
+            values[ignore[i] as usize] = IGNORE;
+            i += 1;
+        }
+    }
+    Base85 { symbols: *symbols, values, ascii85 }
+}
+
+const fn ascii85_symbols() -> [u8; 85] {
+    let mut symbols = [0; 85];
+    let mut i = 0;
+    while i < 85 {
+        symbols[i] = b'!' + i as u8;
+        i += 1;
+    }
+    symbols
+}
+
+/// Adobe Ascii85 encoding
+///
+/// This encoding uses the characters from `!` to `u` (in ASCII order). The encoded output is
+/// delimited by `<~` and `~>`, and a block of 4 zero bytes is encoded as `z`.
+///
+/// When decoding, the delimiters are required, and whitespace characters (space, tabulation, line
+/// feed, form feed, carriage return, and null) are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::base85::ASCII85;
+/// assert_eq!(ASCII85.encode(b"\0\0\0\0Man "), "<~z9jqo^~>");
+/// assert_eq!(ASCII85.decode(b"<~z9jqo^\r\n~>").unwrap(), b"\0\0\0\0Man ");
+/// ```
+pub const ASCII85: Base85 = new(&ascii85_symbols(), true);
+
+/// ZeroMQ Z85 encoding
+///
+/// This encoding uses the
+/// `0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#`
+/// alphabet. It conforms to [ZeroMQ RFC 32] when the input length is a multiple of 4 (partial
+/// blocks are an extension).
+///
+/// [ZeroMQ RFC 32]: https://rfc.zeromq.org/spec/32/
+pub const Z85: Base85 = new(
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+    false,
+);
+
+/// RFC1924 base85 encoding
+///
+/// This encoding uses the
+/// `0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~`
+/// alphabet of [RFC1924]. Note that it encodes blocks of 4 bytes (like Git binary patches and
+/// Python `base64.b85encode`) instead of a single 128-bits number.
+///
+/// [RFC1924]: https://tools.ietf.org/html/rfc1924
+pub const RFC1924: Base85 = new(
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+    false,
+);
+
+impl Base85 {
+    /// Returns the maximum encoded length of an input of length `len`
+    ///
+    /// See [`encode_mut`] for when to use it. The actual encoded length is smaller only for
+    /// [`ASCII85`] when the input contains blocks of 4 zero bytes.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `len` is greater than `usize::MAX / 2`.
+    ///
+    /// [`ASCII85`]: constant.ASCII85.html
+    /// [`encode_mut`]: struct.Base85.html#method.encode_mut
+    #[must_use]
+    pub fn encode_len(&self, len: usize) -> usize {
+        let olen = len / 4 * 5 + if len % 4 == 0 { 0 } else { len % 4 + 1 };
+        if self.ascii85 {
+            olen + 4
+        } else {
+            olen
+        }
+    }
+
+    /// Encodes `input` in `output`
+    ///
+    /// Returns the length of the encoded output. The output bytes after the returned length are
+    /// not meaningful and should not be read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of [`encode_len`] for the `input`
+    /// length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base85::Z85;
+    /// # let mut buffer = vec![0; 100];
+    /// let input = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+    /// let output = &mut buffer[0 .. Z85.encode_len(input.len())];
+    /// let len = Z85.encode_mut(&input, output);
+    /// assert_eq!(&output[0 .. len], b"HelloWorld");
+    /// ```
+    ///
+    /// [`encode_len`]: struct.Base85.html#method.encode_len
+    pub fn encode_mut(&self, input: &[u8], output: &mut [u8]) -> usize {
+        assert_eq!(output.len(), self.encode_len(input.len()));
+        let mut outpos = 0;
+        if self.ascii85 {
+            output[.. 2].copy_from_slice(b"<~");
+            outpos = 2;
+        }
+        for chunk in input.chunks(4) {
+            let mut block = [0u8; 4];
+            block[.. chunk.len()].copy_from_slice(chunk);
+            let mut x = u32::from_be_bytes(block);
+            if self.ascii85 && x == 0 && chunk.len() == 4 {
+                output[outpos] = b'z';
+                outpos += 1;
+                continue;
+            }
+            let mut digits = [0u8; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = self.symbols[(x % 85) as usize];
+                x /= 85;
+            }
+            let len = chunk.len() + 1;
+            output[outpos .. outpos + len].copy_from_slice(&digits[.. len]);
+            outpos += len;
+        }
+        if self.ascii85 {
+            output[outpos .. outpos + 2].copy_from_slice(b"~>");
+            outpos += 2;
+        }
+        outpos
+    }
+
+    /// Returns encoded `input`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base85::RFC1924;
+    /// assert_eq!(RFC1924.encode(b"Hello world"), "NM&qnZy<MXa%^M");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode(&self, input: &[u8]) -> String {
+        let mut output = vec![0u8; self.encode_len(input.len())];
+        let len = self.encode_mut(input, &mut output);
+        output.truncate(len);
+        crate::safety_assert!(output.is_ascii());
+        // SAFETY: Ensured by correctness guarantees of encode_mut (and asserted above).
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+
+    /// Returns the maximum decoded length of an input of length `len`
+    ///
+    /// See [`decode_mut`] for when to use it. The actual decoded length is smaller only for
+    /// [`ASCII85`], which may contain delimiters, ignored characters, and `z` shortcuts.
+    ///
+    /// # Panics
+    ///
+    /// May panic if `len` is greater than `usize::MAX / 4`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `len` is invalid (a single character cannot be decoded). The error kind
+    /// is [`Length`] and the [position] is the greatest valid input length. This is never the
+    /// case for [`ASCII85`], in which case [`decode_mut`] checks the length.
+    ///
+    /// [`ASCII85`]: constant.ASCII85.html
+    /// [`Length`]: ../enum.DecodeKind.html#variant.Length
+    /// [`decode_mut`]: struct.Base85.html#method.decode_mut
+    /// [position]: ../struct.DecodeError.html#structfield.position
+    pub fn decode_len(&self, len: usize) -> Result<usize, DecodeError> {
+        if self.ascii85 {
+            return Ok(len.saturating_sub(2) * 4);
+        }
+        if len % 5 == 1 {
+            return Err(DecodeError { position: len - 1, kind: DecodeKind::Length });
+        }
+        Ok(len / 5 * 4 + (len % 5).saturating_sub(1))
+    }
+
+    /// Decodes `input` in `output`
+    ///
+    /// Returns the length of the decoded output. The output bytes after the returned length are
+    /// not meaningful and should not be read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of [`decode_len`] for the `input`
+    /// length. Also panics if `decode_len` fails for the `input` length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. See [`decode`] for more details. The [`read`]
+    /// first bytes of the input have been successfully decoded to the [`written`] first bytes of
+    /// the output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base85::Z85;
+    /// # let mut buffer = vec![0; 100];
+    /// let input = b"HelloWorld";
+    /// let output = &mut buffer[0 .. Z85.decode_len(input.len()).unwrap()];
+    /// let len = Z85.decode_mut(input, output).unwrap();
+    /// assert_eq!(&output[0 .. len], [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]);
+    /// ```
+    ///
+    /// [`decode_len`]: struct.Base85.html#method.decode_len
+    /// [`decode`]: struct.Base85.html#method.decode
+    /// [`read`]: ../struct.DecodePartial.html#structfield.read
+    /// [`written`]: ../struct.DecodePartial.html#structfield.written
+    pub fn decode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, DecodePartial> {
+        assert_eq!(Ok(output.len()), self.decode_len(input.len()));
+        let mut read = 0;
+        let mut written = 0;
+        let fail = |read, written, position, kind| {
+            Err(DecodePartial { read, written, error: DecodeError { position, kind } })
+        };
+        let mut end = input.len();
+        if self.ascii85 {
+            if let Some(position) = (0 .. 2).find(|&i| input.get(i) != Some(&b"<~"[i])) {
+                if position < input.len() {
+                    return fail(0, 0, position, DecodeKind::Symbol);
+                }
+                return fail(0, 0, input.len(), DecodeKind::Length);
+            }
+            read = 2;
+            end = match input[2 ..].iter().position(|&x| x == b'~') {
+                Some(i) => i + 2,
+                None => input.len(),
+            };
+        }
+        let mut block = [0u8; 5];
+        let mut len = 0;
+        let mut start = 0;
+        let mut last = 0;
+        for (i, &x) in input.iter().enumerate().take(end).skip(read) {
+            if self.ascii85 && x == b'z' {
+                if len != 0 {
+                    return fail(read, written, i, DecodeKind::Symbol);
+                }
+                output[written .. written + 4].copy_from_slice(&[0; 4]);
+                written += 4;
+                read = i + 1;
+                continue;
+            }
+            match self.values[x as usize] {
+                IGNORE => continue,
+                INVALID => return fail(read, written, i, DecodeKind::Symbol),
+                value => block[len] = value,
+            }
+            if len == 0 {
+                start = i;
+            }
+            len += 1;
+            last = i;
+            if len == 5 {
+                let value = match decode_block(&block) {
+                    Some(value) => value,
+                    None => return fail(read, written, start, DecodeKind::Symbol),
+                };
+                output[written .. written + 4].copy_from_slice(&value);
+                written += 4;
+                read = i + 1;
+                len = 0;
+            }
+        }
+        if len == 1 {
+            return fail(read, written, start, DecodeKind::Length);
+        }
+        if len > 1 {
+            // We pad with the greatest symbol such that truncation gives the encoded value.
+            let mut padded = block;
+            for value in &mut padded[len ..] {
+                *value = 84;
+            }
+            let value = match decode_block(&padded) {
+                Some(value) => value,
+                None => return fail(read, written, start, DecodeKind::Symbol),
+            };
+            // We check that the block is canonical by encoding it back.
+            let mut canonical = [0u8; 4];
+            canonical[.. len - 1].copy_from_slice(&value[.. len - 1]);
+            let mut x = u32::from_be_bytes(canonical);
+            for digit in padded.iter_mut().rev() {
+                *digit = (x % 85) as u8;
+                x /= 85;
+            }
+            if padded[.. len] != block[.. len] {
+                return fail(read, written, last, DecodeKind::Trailing);
+            }
+            output[written .. written + len - 1].copy_from_slice(&value[.. len - 1]);
+            written += len - 1;
+        }
+        if self.ascii85 {
+            if end == input.len() {
+                return fail(end, written, input.len(), DecodeKind::Length);
+            }
+            if input.get(end + 1) != Some(&b'>') {
+                let position = end + 1;
+                if position < input.len() {
+                    return fail(end, written, position, DecodeKind::Symbol);
+                }
+                return fail(end, written, input.len(), DecodeKind::Length);
+            }
+            if end + 2 < input.len() {
+                return fail(end + 2, written, end + 2, DecodeKind::Symbol);
+            }
+        }
+        Ok(written)
+    }
+
+    /// Returns decoded `input`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. The error kind can be:
+    ///
+    /// - [`Length`] if the input length is invalid (a single character remains after the last
+    ///   complete block). The [position] is the greatest valid input length. For [`ASCII85`], this
+    ///   is also the case if the input ends before a delimiter, in which case the position is the
+    ///   input length.
+    /// - [`Symbol`] if the input contains an invalid character. The [position] is the first
+    ///   invalid character. This is also the case if a block overflows 32 bits, in which case the
+    ///   position is the first character of the block.
+    /// - [`Trailing`] if the last partial block is not canonical (the non-significant part of its
+    ///   last character is not zero). The [position] is the last character of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::base85::ASCII85;
+    /// assert_eq!(ASCII85.decode(b"<~87cURD]j7BEbo7~>").unwrap(), b"Hello world");
+    /// ```
+    ///
+    /// [`ASCII85`]: constant.ASCII85.html
+    /// [`Length`]: ../enum.DecodeKind.html#variant.Length
+    /// [`Symbol`]: ../enum.DecodeKind.html#variant.Symbol
+    /// [`Trailing`]: ../enum.DecodeKind.html#variant.Trailing
+    /// [position]: ../struct.DecodeError.html#structfield.position
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut output = vec![0u8; self.decode_len(input.len())?];
+        let len = self.decode_mut(input, &mut output).map_err(|partial| partial.error)?;
+        output.truncate(len);
+        Ok(output)
+    }
+}
+
+// Returns the 4 bytes encoded by the 5 values, unless they overflow.
+fn decode_block(block: &[u8; 5]) -> Option<[u8; 4]> {
+    let x = block.iter().fold(0u64, |x, &y| x * 85 + u64::from(y));
+    if x > u64::from(u32::MAX) {
+        return None;
+    }
+    Some((x as u32).to_be_bytes())
+}
@@ -0,0 +1,332 @@
+//! Bech32 and Bech32m encodings
+//!
+//! A Bech32 string is made of a human-readable part (HRP), the `1` separator, the data encoded in
+//! base32 with the `qpzry9x8gf2tvdw0s3jn54khce6mua7l` alphabet (most significant bit first, no
+//! padding, zero trailing bits), and a 6-characters BCH checksum covering both the human-readable
+//! part and the data. Bech32 is defined by [BIP173] and Bech32m by [BIP350]. They only differ by
+//! the checksum constant, which is selected with [`Variant`].
+//!
+//! Strings are at most 90 characters long, may be either all lowercase or all uppercase (encoding
+//! always produces lowercase), and their human-readable part only contains ASCII characters in the
+//! range 33 to 126.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::bech32::{decode, encode, Variant};
+//! let encoded = encode("hello", b"world", Variant::Bech32m).unwrap();
+//! assert_eq!(encoded, "hello1wahhymryxruu7j");
+//! let (hrp, data) = decode(b"HELLO1WAHHYMRYXRUU7J", Variant::Bech32m).unwrap();
+//! assert_eq!(hrp, "hello");
+//! assert_eq!(data, b"world");
+//! ```
+//!
+//! [BIP173]: https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! [BIP350]: https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+//! [`Variant`]: enum.Variant.html
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{DecodeKind, Encoding};
+
+/// Checksum variant
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Variant {
+    /// Bech32 checksum as defined by [BIP173]
+    ///
+    /// [BIP173]: https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+    Bech32,
+
+    /// Bech32m checksum as defined by [BIP350]
+    ///
+    /// [BIP350]: https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+    Bech32m,
+}
+
+impl Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+/// Bech32 error kind
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The string is longer than 90 characters or its checksum is shorter than 6 characters
+    Length,
+
+    /// The string does not contain the `1` separator
+    Separator,
+
+    /// The human-readable part is empty or contains a character outside the range 33 to 126
+    Hrp,
+
+    /// The string mixes lowercase and uppercase characters
+    MixedCase,
+
+    /// The data part contains a character outside the alphabet
+    Symbol,
+
+    /// The data part has an invalid length or non-zero trailing bits
+    Padding,
+
+    /// The checksum does not match
+    Checksum,
+}
+
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let description = match self {
+            ErrorKind::Length => "invalid length",
+            ErrorKind::Separator => "missing separator",
+            ErrorKind::Hrp => "invalid human-readable part",
+            ErrorKind::MixedCase => "mixed case",
+            ErrorKind::Symbol => "invalid symbol",
+            ErrorKind::Padding => "invalid padding",
+            ErrorKind::Checksum => "invalid checksum",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// Bech32 error
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// Error position
+    ///
+    /// For decoding, this is the position in the input of the first encountered error. For
+    /// encoding, this is the position in the human-readable part for [`Hrp`] and [`MixedCase`] and
+    /// the maximum length for [`Length`].
+    ///
+    /// [`Hrp`]: enum.ErrorKind.html#variant.Hrp
+    /// [`Length`]: enum.ErrorKind.html#variant.Length
+    /// [`MixedCase`]: enum.ErrorKind.html#variant.MixedCase
+    pub position: usize,
+
+    /// Error kind
+    pub kind: ErrorKind,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} at {}", self.kind, self.position)
+    }
+}
+
+const MAX_LEN: usize = 90;
+const CHECKSUM_LEN: usize = 6;
+
+// Static version of the `qpzry9x8gf2tvdw0s3jn54khce6mua7l` specification (without padding).
+const DATA: Encoding = Encoding::internal_new(DATA_IMPL);
+const DATA_IMPL: &[u8] = &[
+    113, 112, 122, 114, 121, 57, 120, 56, 103, 102, 50, 116, 118, 100, 119, 48, 115, 51, 106, 110,
+    53, 52, 107, 104, 99, 101, 54, 109, 117, 97, 55, 108, 113, 112, 122, 114, 121, 57, 120, 56,
+    103, 102, 50, 116, 118, 100, 119, 48, 115, 51, 106, 110, 53, 52, 107, 104, 99, 101, 54, 109,
+    117, 97, 55, 108, 113, 112, 122, 114, 121, 57, 120, 56, 103, 102, 50, 116, 118, 100, 119, 48,
+    115, 51, 106, 110, 53, 52, 107, 104, 99, 101, 54, 109, 117, 97, 55, 108, 113, 112, 122, 114,
+    121, 57, 120, 56, 103, 102, 50, 116, 118, 100, 119, 48, 115, 51, 106, 110, 53, 52, 107, 104,
+    99, 101, 54, 109, 117, 97, 55, 108, 113, 112, 122, 114, 121, 57, 120, 56, 103, 102, 50, 116,
+    118, 100, 119, 48, 115, 51, 106, 110, 53, 52, 107, 104, 99, 101, 54, 109, 117, 97, 55, 108,
+    113, 112, 122, 114, 121, 57, 120, 56, 103, 102, 50, 116, 118, 100, 119, 48, 115, 51, 106, 110,
+    53, 52, 107, 104, 99, 101, 54, 109, 117, 97, 55, 108, 113, 112, 122, 114, 121, 57, 120, 56,
+    103, 102, 50, 116, 118, 100, 119, 48, 115, 51, 106, 110, 53, 52, 107, 104, 99, 101, 54, 109,
+    117, 97, 55, 108, 113, 112, 122, 114, 121, 57, 120, 56, 103, 102, 50, 116, 118, 100, 119, 48,
+    115, 51, 106, 110, 53, 52, 107, 104, 99, 101, 54, 109, 117, 97, 55, 108, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 15, 128, 10, 17, 21, 20, 26, 30, 7, 5, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 29, 128, 24, 13, 25,
+    9, 8, 23, 128, 18, 22, 31, 27, 19, 128, 1, 0, 3, 16, 11, 28, 12, 14, 6, 4, 2, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
+    128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 29,
+];
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut check = 1u32;
+    for value in values {
+        let top = check >> 25;
+        check = (check & 0x01ff_ffff) << 5 ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if top >> i & 1 == 1 {
+                check ^= generator;
+            }
+        }
+    }
+    check
+}
+
+fn checksum_values<'a>(hrp: &'a [u8], data: &'a [u8]) -> impl Iterator<Item = u8> + 'a {
+    let hrp = hrp.iter().map(u8::to_ascii_lowercase);
+    let high = hrp.clone().map(|x| x >> 5);
+    let low = hrp.map(|x| x & 31);
+    let data = data.iter().map(|&x| DATA.val()[x.to_ascii_lowercase() as usize]);
+    high.chain(core::iter::once(0)).chain(low).chain(data)
+}
+
+fn check_hrp(hrp: &[u8]) -> Result<(), Error> {
+    if hrp.is_empty() {
+        return Err(Error { position: 0, kind: ErrorKind::Hrp });
+    }
+    match hrp.iter().position(|&x| !(33 ..= 126).contains(&x)) {
+        Some(position) => Err(Error { position, kind: ErrorKind::Hrp }),
+        None => Ok(()),
+    }
+}
+
+fn check_case(input: &[u8]) -> Result<(), Error> {
+    let mut case = None;
+    for (position, &x) in input.iter().enumerate() {
+        let upper = match x {
+            b'A' ..= b'Z' => true,
+            b'a' ..= b'z' => false,
+            _ => continue,
+        };
+        match case {
+            None => case = Some(upper),
+            Some(case) if case != upper => {
+                return Err(Error { position, kind: ErrorKind::MixedCase });
+            }
+            Some(_) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Encodes `data` with the human-readable part `hrp`
+///
+/// The output is always lowercase. The human-readable part may be uppercase, in which case it is
+/// converted to lowercase.
+///
+/// # Errors
+///
+/// Returns an error if the human-readable part is invalid (error kind [`Hrp`] or [`MixedCase`])
+/// or if the output would be longer than 90 characters (error kind [`Length`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::bech32::{encode, ErrorKind, Variant};
+/// assert_eq!(encode("a", b"", Variant::Bech32).unwrap(), "a12uel5l");
+/// assert_eq!(encode("A", b"", Variant::Bech32m).unwrap(), "a1lqfn3a");
+/// assert_eq!(encode("", b"", Variant::Bech32).unwrap_err().kind, ErrorKind::Hrp);
+/// assert_eq!(encode("a", &[0; 60], Variant::Bech32).unwrap_err().kind, ErrorKind::Length);
+/// ```
+///
+/// [`Hrp`]: enum.ErrorKind.html#variant.Hrp
+/// [`Length`]: enum.ErrorKind.html#variant.Length
+/// [`MixedCase`]: enum.ErrorKind.html#variant.MixedCase
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, Error> {
+    let hrp = hrp.as_bytes();
+    check_hrp(hrp)?;
+    check_case(hrp)?;
+    let len = hrp.len() + 1 + DATA.encode_len(data.len()) + CHECKSUM_LEN;
+    if len > MAX_LEN {
+        return Err(Error { position: MAX_LEN, kind: ErrorKind::Length });
+    }
+    let mut output = Vec::with_capacity(len);
+    output.extend(hrp.iter().map(u8::to_ascii_lowercase));
+    output.push(b'1');
+    let start = output.len();
+    output.resize(len, 0);
+    let (encoded, checksum) = output[start ..].split_at_mut(len - start - CHECKSUM_LEN);
+    DATA.encode_mut(data, encoded);
+    let zeros = core::iter::repeat(0).take(CHECKSUM_LEN);
+    let value = polymod(checksum_values(hrp, encoded).chain(zeros)) ^ variant.constant();
+    for (i, output) in checksum.iter_mut().enumerate() {
+        *output = DATA.sym()[(value >> (5 * (CHECKSUM_LEN - 1 - i)) & 31) as usize];
+    }
+    crate::safety_assert!(output.is_ascii());
+    // SAFETY: The human-readable part is checked to be ASCII and the rest is made of symbols.
+    Ok(unsafe { String::from_utf8_unchecked(output) })
+}
+
+/// Decodes `input` and returns its human-readable part and data
+///
+/// The returned human-readable part is always lowercase.
+///
+/// # Errors
+///
+/// Returns an error if `input` is invalid. The errors are checked in this order:
+///
+/// - [`Length`] at position 90 if the input is longer than 90 characters
+/// - [`Separator`] at the input length if the input does not contain `1`
+/// - [`Hrp`] at position 0 if the human-readable part is empty, or at the position of its first
+///   character outside the range 33 to 126
+/// - [`Symbol`] at the position of the first data character outside the alphabet
+/// - [`MixedCase`] at the position of the first character whose case differs from the previous
+///   ones
+/// - [`Length`] at the input length if the checksum is shorter than 6 characters
+/// - [`Checksum`] at the position of the first checksum character if the checksum does not match
+///   `variant`
+/// - [`Padding`] if the data part has an invalid length or non-zero trailing bits
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::bech32::{decode, Error, ErrorKind, Variant};
+/// assert_eq!(decode(b"A12UEL5L", Variant::Bech32).unwrap(), ("a".to_string(), vec![]));
+/// assert_eq!(decode(b"A12uEL5L", Variant::Bech32).unwrap_err(), Error {
+///     position: 3,
+///     kind: ErrorKind::MixedCase
+/// });
+/// assert_eq!(decode(b"a12uel5l", Variant::Bech32m).unwrap_err(), Error {
+///     position: 2,
+///     kind: ErrorKind::Checksum
+/// });
+/// assert_eq!(decode(b" 1nwldj5", Variant::Bech32).unwrap_err().kind, ErrorKind::Hrp);
+/// ```
+///
+/// [`Checksum`]: enum.ErrorKind.html#variant.Checksum
+/// [`Hrp`]: enum.ErrorKind.html#variant.Hrp
+/// [`Length`]: enum.ErrorKind.html#variant.Length
+/// [`MixedCase`]: enum.ErrorKind.html#variant.MixedCase
+/// [`Padding`]: enum.ErrorKind.html#variant.Padding
+/// [`Separator`]: enum.ErrorKind.html#variant.Separator
+/// [`Symbol`]: enum.ErrorKind.html#variant.Symbol
+pub fn decode(input: &[u8], variant: Variant) -> Result<(String, Vec<u8>), Error> {
+    if input.len() > MAX_LEN {
+        return Err(Error { position: MAX_LEN, kind: ErrorKind::Length });
+    }
+    let separator = match input.iter().rposition(|&x| x == b'1') {
+        Some(x) => x,
+        None => return Err(Error { position: input.len(), kind: ErrorKind::Separator }),
+    };
+    let (hrp, data) = (&input[.. separator], &input[separator + 1 ..]);
+    check_hrp(hrp)?;
+    if let Some(i) = data.iter().position(|&x| DATA.val()[x.to_ascii_lowercase() as usize] >= 32) {
+        return Err(Error { position: separator + 1 + i, kind: ErrorKind::Symbol });
+    }
+    check_case(input)?;
+    if data.len() < CHECKSUM_LEN {
+        return Err(Error { position: input.len(), kind: ErrorKind::Length });
+    }
+    if polymod(checksum_values(hrp, data)) != variant.constant() {
+        let position = input.len() - CHECKSUM_LEN;
+        return Err(Error { position, kind: ErrorKind::Checksum });
+    }
+    let data: Vec<u8> =
+        data[.. data.len() - CHECKSUM_LEN].iter().map(u8::to_ascii_lowercase).collect();
+    let data = DATA.decode(&data).map_err(|error| Error {
+        position: separator + 1 + error.position,
+        kind: match error.kind {
+            DecodeKind::Symbol => ErrorKind::Symbol,
+            _ => ErrorKind::Padding,
+        },
+    })?;
+    let hrp = hrp.iter().map(|&x| char::from(x.to_ascii_lowercase())).collect();
+    Ok((hrp, data))
+}
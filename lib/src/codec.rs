@@ -0,0 +1,237 @@
+//! Tokio codec support
+//!
+//! This module provides [`LineCodec`] to frame a stream as separator-terminated lines, each line
+//! being the encoding of one frame. This is useful for line protocols sending one encoded record
+//! per line (e.g. base64 in SMTP AUTH).
+//!
+//! This module needs the `bytes` and `tokio-util` features (and Rust 1.49).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bytes::BytesMut;
+//! use data_encoding::codec::LineCodec;
+//! use tokio_util::codec::{Decoder, Encoder};
+//! // See the documentation of Encoder for why we need a static.
+//! static BASE64: data_encoding::Encoding = data_encoding::BASE64;
+//!
+//! let mut codec = LineCodec::new(&BASE64).separator(b"\r\n").max_length(76);
+//! let mut buffer = BytesMut::new();
+//! codec.encode(b"hello", &mut buffer).unwrap();
+//! codec.encode(b"world", &mut buffer).unwrap();
+//! assert_eq!(buffer, &b"aGVsbG8=\r\nd29ybGQ=\r\n"[..]);
+//!
+//! assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), b"hello");
+//! assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), b"world");
+//! assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+//! ```
+//!
+//! [`LineCodec`]: struct.LineCodec.html
+
+use std::vec::Vec;
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{DecodeError, Encoding};
+
+/// Codec for separator-terminated lines of encoded frames
+///
+/// Decoding splits the input on the separator (which is not part of the line) and decodes each
+/// line with the encoding. At the end of the input, a last line without separator is also decoded.
+/// Encoding encodes the frame and appends the separator.
+///
+/// Lines longer than the maximum length (excluding the separator) are rejected with
+/// [`LineCodecError::MaxLength`]. When decoding, such a line is discarded (up to the next
+/// separator) and decoding can continue. Invalid lines are rejected with
+/// [`LineCodecError::Decode`] and are also discarded.
+///
+/// [`LineCodecError::Decode`]: enum.LineCodecError.html#variant.Decode
+/// [`LineCodecError::MaxLength`]: enum.LineCodecError.html#variant.MaxLength
+#[derive(Debug, Clone)]
+pub struct LineCodec {
+    encoding: &'static Encoding,
+    separator: &'static [u8],
+    max_length: usize,
+    // Position from which to search for the separator.
+    next_index: usize,
+    // Whether the current line is too long and is being discarded.
+    discarding: bool,
+}
+
+/// Line codec error
+#[derive(Debug)]
+pub enum LineCodecError {
+    /// A line is longer than the maximum length
+    MaxLength,
+
+    /// A line is not valid for the encoding
+    ///
+    /// The position is relative to the beginning of the line.
+    Decode(DecodeError),
+
+    /// The underlying reader or writer failed
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for LineCodecError {
+    fn from(error: std::io::Error) -> Self {
+        LineCodecError::Io(error)
+    }
+}
+
+impl From<DecodeError> for LineCodecError {
+    fn from(error: DecodeError) -> Self {
+        LineCodecError::Decode(error)
+    }
+}
+
+impl core::fmt::Display for LineCodecError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LineCodecError::MaxLength => write!(f, "line too long"),
+            LineCodecError::Decode(error) => write!(f, "invalid line: {}", error),
+            LineCodecError::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LineCodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LineCodecError::MaxLength => None,
+            LineCodecError::Decode(error) => Some(error),
+            LineCodecError::Io(error) => Some(error),
+        }
+    }
+}
+
+impl LineCodec {
+    /// Creates a line codec for an encoding
+    ///
+    /// The separator is `\n` and there is no maximum length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the encoding wraps lines with the separator (e.g. [`BASE64_MIME`]), since the
+    /// encoding of a frame would then span multiple lines.
+    ///
+    /// [`BASE64_MIME`]: ../constant.BASE64_MIME.html
+    #[must_use]
+    pub fn new(encoding: &'static Encoding) -> Self {
+        check_wrap(encoding, b"\n");
+        LineCodec {
+            encoding,
+            separator: b"\n",
+            max_length: usize::MAX,
+            next_index: 0,
+            discarding: false,
+        }
+    }
+
+    /// Sets the line separator
+    ///
+    /// # Panics
+    ///
+    /// Panics if the separator is empty or if the encoding wraps lines with the separator.
+    #[must_use]
+    pub fn separator(mut self, separator: &'static [u8]) -> Self {
+        assert!(!separator.is_empty(), "separator must not be empty");
+        check_wrap(self.encoding, separator);
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the maximum length of a line (excluding the separator)
+    #[must_use]
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    fn decode_line(&self, line: &[u8]) -> Result<Vec<u8>, LineCodecError> {
+        if line.len() > self.max_length {
+            return Err(LineCodecError::MaxLength);
+        }
+        Ok(self.encoding.decode(line)?)
+    }
+}
+
+// Panics if the wrapping characters of the encoding may contain the separator.
+fn check_wrap(encoding: &Encoding, separator: &[u8]) {
+    if let Some((_, prefix, end)) = encoding.wrap() {
+        let wrap = [end, prefix].concat();
+        let found = wrap.windows(separator.len()).any(|x| x == separator);
+        assert!(!found, "separator must not appear in wrapped lines");
+    }
+}
+
+impl Decoder for LineCodec {
+    type Item = Vec<u8>;
+    type Error = LineCodecError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Vec<u8>>, LineCodecError> {
+        let len = self.separator.len();
+        loop {
+            let found = buf[self.next_index ..].windows(len).position(|x| x == self.separator);
+            let found = found.map(|i| self.next_index + i);
+            // Length of the buffer that can't contain the beginning of a separator.
+            let partial = buf.len().saturating_sub(len - 1);
+            match found {
+                Some(i) => {
+                    self.next_index = 0;
+                    let line = buf.split_to(i + len);
+                    if self.discarding {
+                        self.discarding = false;
+                        continue;
+                    }
+                    return self.decode_line(&line[.. i]).map(Some);
+                }
+                None if self.discarding => {
+                    buf.advance(partial);
+                    self.next_index = 0;
+                    return Ok(None);
+                }
+                None if partial > self.max_length => {
+                    self.discarding = true;
+                    self.next_index = partial;
+                    return Err(LineCodecError::MaxLength);
+                }
+                None => {
+                    self.next_index = partial;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Vec<u8>>, LineCodecError> {
+        if let Some(frame) = self.decode(buf)? {
+            return Ok(Some(frame));
+        }
+        let line = buf.split();
+        self.next_index = 0;
+        if core::mem::replace(&mut self.discarding, false) || line.is_empty() {
+            return Ok(None);
+        }
+        self.decode_line(&line).map(Some)
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for LineCodec {
+    type Error = LineCodecError;
+
+    fn encode(&mut self, item: T, buf: &mut BytesMut) -> Result<(), LineCodecError> {
+        let item = item.as_ref();
+        let len = self.encoding.encode_len(item.len());
+        if len > self.max_length {
+            return Err(LineCodecError::MaxLength);
+        }
+        let start = buf.len();
+        buf.reserve(len + self.separator.len());
+        buf.resize(start + len, 0);
+        self.encoding.encode_mut(item, &mut buf[start ..]);
+        buf.extend_from_slice(self.separator);
+        Ok(())
+    }
+}
@@ -0,0 +1,409 @@
+//! Hexadecimal dumps
+//!
+//! A [`HexDump`] displays bytes like the `xxd` program: each line starts with the offset of its
+//! first byte, followed by the bytes in hexadecimal (by groups), and ends with the bytes in ASCII
+//! (non-printable bytes are displayed as a dot).
+//!
+//! The [`parse`] function does the opposite for the formats of `xxd`, `hexdump -C`, and `od -x`.
+//! The [`parse_with_limit`] function additionally bounds the output length for untrusted inputs.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::hexdump::HexDump;
+//! let input = b"Hello world!\nThis is \x00\x01\xff binary";
+//! assert_eq!(
+//!     HexDump::new(input).to_string(),
+//!     "00000000: 4865 6c6c 6f20 776f 726c 6421 0a54 6869  Hello world!.Thi\n\
+//!      00000010: 7320 6973 2000 01ff 2062 696e 6172 79    s is ... binary\n"
+//! );
+//! ```
+//!
+//! [`parse`]: fn.parse.html
+//! [`parse_with_limit`]: fn.parse_with_limit.html
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::{DecodeError, DecodeKind, HEXLOWER_PERMISSIVE};
+use crate::{HEXLOWER, HEXUPPER};
+
+/// Hexadecimal dump of bytes
+///
+/// See the [module] documentation for more details.
+///
+/// [module]: index.html
+#[derive(Debug, Clone, Copy)]
+pub struct HexDump<'a> {
+    input: &'a [u8],
+    width: usize,
+    group: usize,
+    offset: usize,
+    uppercase: bool,
+}
+
+impl<'a> HexDump<'a> {
+    /// Returns the dump of `input`
+    ///
+    /// By default, lines have 16 bytes by groups of 2, the first offset is 0, and the hexadecimal
+    /// digits are lowercase. This is the default format of `xxd`.
+    #[must_use]
+    pub fn new(input: &'a [u8]) -> Self {
+        HexDump { input, width: 16, group: 2, offset: 0, uppercase: false }
+    }
+
+    /// Sets the number of bytes per line
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::hexdump::HexDump;
+    /// assert_eq!(
+    ///     HexDump::new(b"Hello world!").width(8).to_string(),
+    ///     "00000000: 4865 6c6c 6f20 776f  Hello wo\n\
+    ///      00000008: 726c 6421            rld!\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn width(mut self, width: usize) -> Self {
+        assert!(width > 0, "width must be positive");
+        self.width = width;
+        self
+    }
+
+    /// Sets the number of bytes per group
+    ///
+    /// Groups are separated by a space. A group of zero means no grouping (a line has a single
+    /// group).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::hexdump::HexDump;
+    /// assert_eq!(HexDump::new(b"Hello").group(1).to_string(),
+    ///            "00000000: 48 65 6c 6c 6f                                   Hello\n");
+    /// assert_eq!(HexDump::new(b"Hello").group(0).to_string(),
+    ///            "00000000: 48656c6c6f                        Hello\n");
+    /// ```
+    #[must_use]
+    pub fn group(mut self, group: usize) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// Sets the offset of the first byte
+    ///
+    /// This is useful when dumping a slice of a larger input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::hexdump::HexDump;
+    /// assert_eq!(HexDump::new(b"Hello").offset(0x1000).to_string(),
+    ///            "00001000: 4865 6c6c 6f                             Hello\n");
+    /// ```
+    #[must_use]
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets whether hexadecimal digits are uppercase (including offsets)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::hexdump::HexDump;
+    /// assert_eq!(HexDump::new(b"\xca\xfe").offset(0xff).uppercase(true).to_string(),
+    ///            "000000FF: CAFE                                     ..\n");
+    /// ```
+    #[must_use]
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    // Returns the length of the hexadecimal column of a line with `len` bytes.
+    fn hex_len(&self, len: usize) -> usize {
+        let groups = match self.group {
+            0 => 1,
+            group => (len + group - 1) / group,
+        };
+        2 * len + groups.saturating_sub(1)
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = if self.uppercase { HEXUPPER } else { HEXLOWER };
+        let group = if self.group == 0 { self.width } else { self.group };
+        let width = self.hex_len(self.width);
+        for (i, line) in self.input.chunks(self.width).enumerate() {
+            let offset = self.offset.wrapping_add(i * self.width);
+            if self.uppercase {
+                write!(f, "{:08X}: ", offset)?;
+            } else {
+                write!(f, "{:08x}: ", offset)?;
+            }
+            for (j, group) in line.chunks(group).enumerate() {
+                if j > 0 {
+                    f.write_str(" ")?;
+                }
+                hex.encode_write(group, f)?;
+            }
+            write!(f, "{:1$}  ", "", width - self.hex_len(line.len()))?;
+            for &x in line {
+                let x = if x == b' ' || x.is_ascii_graphic() { x } else { b'.' };
+                fmt::Write::write_char(f, char::from(x))?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Format of a hexadecimal dump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Format of `xxd` (and [`HexDump`])
+    ///
+    /// Lines look like `00000000: 4865 6c6c 6f  Hello` where the offset is hexadecimal and the
+    /// ASCII column starts after 2 spaces. Groups may have any size.
+    ///
+    /// [`HexDump`]: struct.HexDump.html
+    Xxd,
+
+    /// Format of `hexdump -C`
+    ///
+    /// Lines look like `00000000  48 65 6c 6c 6f  |Hello|` where the offset is hexadecimal and the
+    /// ASCII column starts with `|`. The last line only contains the total length.
+    Hexdump,
+
+    /// Format of `od -x` on little-endian machines
+    ///
+    /// Lines look like `0000000 6548 6c6c 006f` where the offset is octal and the 16-bits words
+    /// are little-endian. The last line only contains the total length (which truncates the
+    /// padding of the last word).
+    Od,
+}
+
+/// Parses a hexadecimal dump
+///
+/// Offsets and ASCII columns are skipped. A line containing only `*` (as produced when squeezing
+/// identical lines) repeats the previous line until the offset of the next line. A line containing
+/// only an offset marks the end of the dump (and truncates it if needed). Empty lines are ignored
+/// and lines may end with `\r\n`. Hexadecimal digits are case-insensitive.
+///
+/// # Errors
+///
+/// Returns an error if `input` is invalid. The error [position] is the index in `input` of the
+/// invalid character. The error kind can be:
+///
+/// - [`Symbol`] if a character is invalid (e.g. in the offset or the hexadecimal columns)
+/// - [`Length`] if a group has an odd number of digits (or not 4 digits for [`Od`]), or if a
+///   squeezed line doesn't repeat an integer number of times
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::hexdump::{parse, Format};
+/// use data_encoding::{DecodeError, DecodeKind};
+/// let xxd = b"00000000: 4865 6c6c 6f20 776f 726c 6421 0a54 6869  Hello world!.Thi\n\
+///             00000010: 7320 6973 2000 01ff 2062 696e 6172 79    s is ... binary\n";
+/// let hexdump = b"00000000  48 65 6c 6c 6f 20 77 6f  72 6c 64 21 0a 54 68 69  |Hello world!.Thi|\n\
+///                 00000010  73 20 69 73 20 00 01 ff  20 62 69 6e 61 72 79     |s is ... binary|\n\
+///                 0000001f\n";
+/// let od = b"0000000 6548 6c6c 206f 6f77 6c72 2164 540a 6968\n\
+///            0000020 2073 7369 0020 ff01 6220 6e69 7261 0079\n\
+///            0000037\n";
+/// let output = b"Hello world!\nThis is \x00\x01\xff binary";
+/// assert_eq!(parse(xxd, Format::Xxd).unwrap(), output);
+/// assert_eq!(parse(hexdump, Format::Hexdump).unwrap(), output);
+/// assert_eq!(parse(od, Format::Od).unwrap(), output);
+/// assert_eq!(parse(b"00000000: 4865 6g6c  He.l", Format::Xxd).unwrap_err(), DecodeError {
+///     position: 16,
+///     kind: DecodeKind::Symbol,
+/// });
+/// ```
+///
+/// [`Length`]: ../enum.DecodeKind.html#variant.Length
+/// [`Od`]: enum.Format.html#variant.Od
+/// [`Symbol`]: ../enum.DecodeKind.html#variant.Symbol
+/// [position]: ../struct.DecodeError.html#structfield.position
+#[cfg(feature = "alloc")]
+pub fn parse(input: &[u8], format: Format) -> Result<Vec<u8>, DecodeError> {
+    parse_with_limit(input, format, usize::MAX)
+}
+
+/// Parses a hexadecimal dump of at most `max_len` bytes
+///
+/// This function behaves like [`parse`] but fails if the output would be longer than `max_len`.
+/// Squeezed lines may describe arbitrarily long outputs with a few input bytes, so this function
+/// should be preferred for untrusted inputs.
+///
+/// # Errors
+///
+/// Returns an error if `input` is invalid. See [`parse`] for more details. Additionally, the
+/// error kind is [`Length`] if the output would be longer than `max_len`, in which case the
+/// position is the one of the squeezed line or of the hexadecimal columns exceeding the limit.
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::hexdump::{parse_with_limit, Format};
+/// use data_encoding::{DecodeError, DecodeKind};
+/// let xxd = b"00000000: 0000\n*\n00100000:\n";
+/// assert_eq!(parse_with_limit(xxd, Format::Xxd, 1 << 16).unwrap_err(), DecodeError {
+///     position: 15,
+///     kind: DecodeKind::Length,
+/// });
+/// assert_eq!(parse_with_limit(xxd, Format::Xxd, 1 << 20).unwrap(), [0; 1 << 20]);
+/// ```
+///
+/// [`Length`]: ../enum.DecodeKind.html#variant.Length
+/// [`parse`]: fn.parse.html
+#[cfg(feature = "alloc")]
+pub fn parse_with_limit(
+    input: &[u8], format: Format, max_len: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    let error = |position, kind| DecodeError { position, kind };
+    let hex = {
+        let mut spec = HEXLOWER_PERMISSIVE.specification();
+        spec.ignore.push(' ');
+        spec.encoding().unwrap()
+    };
+    let mut output = Vec::new();
+    // Offset of the first line and output range of the previous line.
+    let mut base = None;
+    let mut previous = 0 .. 0;
+    // Position of the last squeeze line (if the previous line was squeezed).
+    let mut squeeze = None;
+    let mut start = 0;
+    for line in input.split(|&x| x == b'\n') {
+        let position = start;
+        start += line.len() + 1;
+        let line = match line.split_last() {
+            Some((b'\r', line)) => line,
+            _ => line,
+        };
+        if line.iter().all(|&x| x == b' ') {
+            continue;
+        }
+        if line == b"*" {
+            if previous.is_empty() {
+                return Err(error(position, DecodeKind::Symbol));
+            }
+            squeeze = Some(position);
+            continue;
+        }
+        let radix = if format == Format::Od { 8 } else { 16 };
+        let (offset, len) =
+            parse_offset(line, radix).map_err(|i| error(position + i, DecodeKind::Symbol))?;
+        let offset = offset.wrapping_sub(*base.get_or_insert(offset));
+        if let Some(squeeze) = squeeze.take() {
+            let count = offset.checked_sub(output.len()).filter(|x| x % previous.len() == 0);
+            let count = count.filter(|_| offset <= max_len);
+            let count = count.ok_or_else(|| error(squeeze, DecodeKind::Length))?;
+            for _ in 0 .. count / previous.len() {
+                for i in previous.clone() {
+                    output.push(output[i]);
+                }
+            }
+        }
+        let mut line = &line[len ..];
+        let mut position = position + len;
+        match format {
+            Format::Xxd => {
+                if line.first() != Some(&b':') {
+                    return Err(error(position, DecodeKind::Symbol));
+                }
+                line = &line[1 ..];
+                position += 1;
+                if let Some(end) = line.windows(2).position(|x| x == b"  ") {
+                    line = &line[.. end];
+                }
+            }
+            Format::Hexdump | Format::Od if !line.is_empty() && line[0] != b' ' => {
+                return Err(error(position, DecodeKind::Symbol));
+            }
+            Format::Hexdump => {
+                if let Some(end) = line.iter().position(|&x| x == b'|') {
+                    line = &line[.. end];
+                }
+            }
+            Format::Od => (),
+        }
+        let begin = output.len();
+        if line.iter().all(|&x| x == b' ') {
+            output.truncate(offset);
+        } else if format == Format::Od {
+            let mut i = 0;
+            for word in line.split(|&x| x == b' ') {
+                if !word.is_empty() {
+                    let mut buffer = [0; 2];
+                    let shift = |mut e: DecodeError| {
+                        e.position += position + i;
+                        e
+                    };
+                    check_len(word, 4).map_err(shift)?;
+                    let _ = HEXLOWER_PERMISSIVE
+                        .decode_mut(word, &mut buffer)
+                        .map_err(|partial| shift(partial.error))?;
+                    output.extend_from_slice(&[buffer[1], buffer[0]]);
+                }
+                i += word.len() + 1;
+            }
+        } else {
+            let decoded = hex.decode(line).map_err(|mut e| {
+                e.position += position;
+                e
+            })?;
+            output.extend_from_slice(&decoded);
+        }
+        if output.len() > max_len {
+            return Err(error(position, DecodeKind::Length));
+        }
+        previous = begin .. output.len();
+    }
+    if let Some(squeeze) = squeeze {
+        return Err(error(squeeze, DecodeKind::Length));
+    }
+    Ok(output)
+}
+
+// Parses the offset at the beginning of `line` and returns its value and length.
+//
+// Fails with the position of the first invalid character.
+#[cfg(feature = "alloc")]
+fn parse_offset(line: &[u8], radix: u32) -> Result<(usize, usize), usize> {
+    let mut offset = 0usize;
+    let mut len = 0;
+    for &x in line {
+        let digit = match char::from(x).to_digit(radix) {
+            Some(digit) => digit as usize,
+            None => break,
+        };
+        offset = offset.checked_mul(radix as usize).ok_or(len)? + digit;
+        len += 1;
+    }
+    match len {
+        0 => Err(0),
+        _ => Ok((offset, len)),
+    }
+}
+
+// Fails with a length error if `word` doesn't have `len` characters.
+#[cfg(feature = "alloc")]
+fn check_len(word: &[u8], len: usize) -> Result<(), DecodeError> {
+    if word.len() == len {
+        return Ok(());
+    }
+    let position = core::cmp::min(word.len(), len);
+    Err(DecodeError { position, kind: DecodeKind::Length })
+}
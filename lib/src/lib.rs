@@ -11,8 +11,24 @@
 //! - most and least significant [bit-order]
 //! - [ignoring] characters when decoding (e.g. for skipping newlines)
 //! - [wrapping] the output when encoding
+//! - [detecting] which predefined encodings match an input (with `features = ["alloc"]`)
 //! - no-std environments with `default-features = false, features = ["alloc"]`
 //! - no-alloc environments with `default-features = false`
+//! - [base45] encoding (RFC9285)
+//! - [base58] encodings (including Base58Check)
+//! - [base85] encodings (Ascii85, Z85, and RFC1924)
+//! - [bech32] and Bech32m encodings (with `features = ["alloc"]`)
+//! - [hexdump] display and parsing (like `xxd`)
+//! - [PEM] textual encoding (RFC7468) with `features = ["alloc"]`
+//! - [unicode] symbols (e.g. Braille patterns or emojis) with `features = ["alloc"]`
+//! - [bytes] buffers (possibly non-contiguous) with `features = ["alloc", "bytes"]`
+//! - [serde] adapters for byte fields with `features = ["alloc", "serde"]` (requires Rust 1.56)
+//! - [tokio] asynchronous readers and writers with `features = ["tokio"]`
+//! - [parallel] encoding and decoding of large inputs with `features = ["rayon"]`
+//! - [tokio codec] for lines of encoded frames with `features = ["bytes", "tokio-util"]` (requires
+//!   Rust 1.49)
+//! - SIMD for bases of size 16 and 64 on x86_64 (and aarch64 with `features = ["neon"]`, which
+//!   requires Rust 1.59)
 //!
 //! You may use the [binary] or the [website] to play around.
 //!
@@ -128,12 +144,25 @@
 //! [`HEXUPPER`]: constant.HEXUPPER.html
 //! [`Specification`]: struct.Specification.html
 //! [`is_canonical`]: struct.Encoding.html#method.is_canonical
+//! [base45]: base45/index.html
+//! [base58]: base58/index.html
+//! [base85]: base85/index.html
+//! [bech32]: bech32/index.html
+//! [serde]: serde/index.html
+//! [tokio]: tokio/index.html
+//! [tokio codec]: codec/index.html
+//! [parallel]: struct.Encoding.html#method.par_encode_mut
+//! [unicode]: unicode/index.html
 //! [binary]: https://crates.io/crates/data-encoding-bin
+//! [bytes]: struct.Encoding.html#method.encode_buf
+//! [hexdump]: hexdump/index.html
+//! [PEM]: pem/index.html
 //! [bit-order]: struct.Specification.html#structfield.bit_order
 //! [canonical]: https://tools.ietf.org/html/rfc4648#section-3.5
 //! [constants]: index.html#constants
 //! [crate]: https://crates.io/crates/data-encoding
 //! [decoding]: struct.Encoding.html#method.decode_mut
+//! [detecting]: fn.detect.html
 //! [encoding]: struct.Encoding.html#method.encode_mut
 //! [ignoring]: struct.Specification.html#structfield.ignore
 //! [macro]: https://crates.io/crates/data-encoding-macro
@@ -151,6 +180,9 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(all(feature = "bytes", not(feature = "alloc")))]
+compile_error!("the `bytes` feature requires the `alloc` feature");
+
 #[cfg(feature = "alloc")]
 use alloc::borrow::{Cow, ToOwned};
 #[cfg(feature = "alloc")]
@@ -162,6 +194,24 @@ use alloc::vec::Vec;
 use core::convert::TryInto;
 use core::debug_assert as safety_assert;
 
+pub mod base45;
+pub mod base58;
+pub mod base85;
+#[cfg(feature = "alloc")]
+pub mod bech32;
+#[cfg(all(feature = "std", feature = "bytes", feature = "tokio-util"))]
+pub mod codec;
+pub mod hexdump;
+#[cfg(feature = "alloc")]
+pub mod pem;
+#[cfg(all(feature = "alloc", feature = "serde"))]
+pub mod serde;
+mod simd;
+#[cfg(all(feature = "std", feature = "tokio"))]
+pub mod tokio;
+#[cfg(feature = "alloc")]
+pub mod unicode;
+
 macro_rules! check {
     ($e: expr, $c: expr) => {
         if !$c {
@@ -393,6 +443,12 @@ fn encode_mut<B: Static<usize>, M: Static<bool>>(
     debug_assert_eq!(output.len(), encode_len(bit, input.len()));
     let enc = enc(bit.val());
     let dec = dec(bit.val());
+    let (input, output) = if msb.val() && simd::supports(bit.val()) {
+        let len = simd::encode(bit.val(), symbols, input, output);
+        (&input[len ..], &mut output[len / enc * dec ..])
+    } else {
+        (input, output)
+    };
     let n = input.len() / enc;
     let bs = match bit.val() {
         5 => 2,
@@ -437,14 +493,20 @@ fn decode_mut<B: Static<usize>, M: Static<bool>>(
     debug_assert_eq!(input.len(), encode_len(bit, output.len()));
     let enc = enc(bit.val());
     let dec = dec(bit.val());
+    let (offset, input, output) = if msb.val() && simd::supports(bit.val()) {
+        let len = simd::decode(bit.val(), values, input, output);
+        (len, &input[len ..], &mut output[len / dec * enc ..])
+    } else {
+        (0, input, output)
+    };
     let n = input.len() / dec;
     for i in 0 .. n {
         let input = chunk_unchecked(input, dec, i);
         let output = chunk_mut_unchecked(output, enc, i);
-        decode_block(bit, msb, values, input, output).map_err(|e| dec * i + e)?;
+        decode_block(bit, msb, values, input, output).map_err(|e| offset + dec * i + e)?;
     }
     decode_block(bit, msb, values, &input[dec * n ..], &mut output[enc * n ..])
-        .map_err(|e| dec * n + e)
+        .map_err(|e| offset + dec * n + e)
 }
 
 // Fails if there are non-zero trailing bits.
@@ -515,14 +577,14 @@ fn encode_wrap_len<
     'a,
     B: Static<usize>,
     P: Static<Option<u8>>,
-    W: Static<Option<(usize, &'a [u8])>>,
+    W: Static<Option<(usize, &'a [u8], &'a [u8])>>,
 >(
     bit: B, pad: P, wrap: W, ilen: usize,
 ) -> usize {
     let olen = encode_pad_len(bit, pad, ilen);
     match wrap.val() {
         None => olen,
-        Some((col, end)) => olen + end.len() * div_ceil(olen, col),
+        Some((col, prefix, end)) => olen + (prefix.len() + end.len()) * div_ceil(olen, col),
     }
 }
 
@@ -531,30 +593,33 @@ fn encode_wrap_mut<
     B: Static<usize>,
     M: Static<bool>,
     P: Static<Option<u8>>,
-    W: Static<Option<(usize, &'a [u8])>>,
+    W: Static<Option<(usize, &'a [u8], &'a [u8])>>,
 >(
     bit: B, msb: M, symbols: &[u8; 256], pad: P, wrap: W, input: &[u8], output: &mut [u8],
 ) {
-    let (col, end) = match wrap.val() {
+    let (col, prefix, end) = match wrap.val() {
         None => return encode_pad(bit, msb, symbols, pad, input, output),
-        Some((col, end)) => (col, end),
+        Some((col, prefix, end)) => (col, prefix, end),
     };
     debug_assert_eq!(output.len(), encode_wrap_len(bit, pad, wrap, input.len()));
     debug_assert_eq!(col % dec(bit.val()), 0);
     let col = col / dec(bit.val());
     let enc = col * enc(bit.val());
-    let dec = col * dec(bit.val()) + end.len();
+    let dec = prefix.len() + col * dec(bit.val()) + end.len();
     let olen = dec - end.len();
     let n = input.len() / enc;
     for i in 0 .. n {
         let input = chunk_unchecked(input, enc, i);
         let output = chunk_mut_unchecked(output, dec, i);
-        encode_base(bit, msb, symbols, input, &mut output[.. olen]);
+        output[.. prefix.len()].copy_from_slice(prefix);
+        encode_base(bit, msb, symbols, input, &mut output[prefix.len() .. olen]);
         output[olen ..].copy_from_slice(end);
     }
     if input.len() > enc * n {
-        let olen = dec * n + encode_pad_len(bit, pad, input.len() - enc * n);
-        encode_pad(bit, msb, symbols, pad, &input[enc * n ..], &mut output[dec * n .. olen]);
+        let start = dec * n + prefix.len();
+        let olen = start + encode_pad_len(bit, pad, input.len() - enc * n);
+        output[dec * n .. start].copy_from_slice(prefix);
+        encode_pad(bit, msb, symbols, pad, &input[enc * n ..], &mut output[start .. olen]);
         output[olen ..].copy_from_slice(end);
     }
 }
@@ -688,18 +753,12 @@ fn skip_ignore(values: &[u8; 256], input: &[u8], mut inpos: usize) -> usize {
     inpos
 }
 
-// Returns next input and output position.
-// Fails with Symbol if an input character does not translate to a symbol. The
-// error is the lowest index of such character.
-// Fails with Padding if some padding length is invalid. The error is the index
-// of the first padding character of the invalid padding.
-// Fails with Trailing if there are non-zero trailing bits.
-fn decode_wrap_block<B: Static<usize>, M: Static<bool>, P: Static<bool>>(
-    bit: B, msb: M, ctb: bool, values: &[u8; 256], pad: P, input: &[u8], output: &mut [u8],
-) -> Result<(usize, usize), DecodeError> {
+// Copies the next block (skipping ignored characters) to `buf` and the position of its characters
+// to `shift`. Returns the block length and the next input position.
+fn wrap_block<B: Static<usize>>(
+    bit: B, values: &[u8; 256], input: &[u8], buf: &mut [u8; 8], shift: &mut [usize; 8],
+) -> (usize, usize) {
     let dec = dec(bit.val());
-    let mut buf = [0u8; 8];
-    let mut shift = [0usize; 8];
     let mut bufpos = 0;
     let mut inpos = 0;
     while bufpos < dec {
@@ -712,6 +771,21 @@ fn decode_wrap_block<B: Static<usize>, M: Static<bool>, P: Static<bool>>(
         bufpos += 1;
         inpos += 1;
     }
+    (bufpos, inpos)
+}
+
+// Returns next input and output position.
+// Fails with Symbol if an input character does not translate to a symbol. The
+// error is the lowest index of such character.
+// Fails with Padding if some padding length is invalid. The error is the index
+// of the first padding character of the invalid padding.
+// Fails with Trailing if there are non-zero trailing bits.
+fn decode_wrap_block<B: Static<usize>, M: Static<bool>, P: Static<bool>>(
+    bit: B, msb: M, ctb: bool, values: &[u8; 256], pad: P, input: &[u8], output: &mut [u8],
+) -> Result<(usize, usize), DecodeError> {
+    let mut buf = [0u8; 8];
+    let mut shift = [0usize; 8];
+    let (bufpos, inpos) = wrap_block(bit, values, input, &mut buf, &mut shift);
     let olen = decode_pad_len(bit, pad, bufpos).map_err(|mut e| {
         e.position = shift[e.position];
         e
@@ -784,6 +858,121 @@ fn decode_wrap_mut<B: Static<usize>, M: Static<bool>, P: Static<bool>, I: Static
     }
 }
 
+// Fails if an input character does not translate to a symbol. The error is the
+// lowest index of such character.
+fn check_symbols<B: Static<usize>>(bit: B, values: &[u8; 256], input: &[u8]) -> Result<(), usize> {
+    let offset = simd::check(values, input);
+    // Symbols are the only characters with a value smaller than 1 << bit. We first check whole
+    // chunks without branching.
+    const CHUNK: usize = 64;
+    for (i, chunk) in input[offset ..].chunks(CHUNK).enumerate() {
+        if chunk.iter().fold(0, |acc, &x| acc | values[x as usize]) >> bit.val() != 0 {
+            let invalid = |x: &u8| values[*x as usize] >> bit.val() != 0;
+            return Err(offset + CHUNK * i + chunk.iter().position(invalid).unwrap());
+        }
+    }
+    Ok(())
+}
+
+// Same as decode_base_mut without output.
+fn validate_base<B: Static<usize>, M: Static<bool>>(
+    bit: B, msb: M, ctb: bool, values: &[u8; 256], input: &[u8],
+) -> Result<(), DecodeError> {
+    check_symbols(bit, values, input)
+        .map_err(|position| DecodeError { position, kind: DecodeKind::Symbol })?;
+    check_trail(bit, msb, ctb, values, input)
+        .map_err(|()| DecodeError { position: input.len() - 1, kind: DecodeKind::Trailing })
+}
+
+// Same as decode_pad_mut without output.
+fn validate_pad<B: Static<usize>, M: Static<bool>, P: Static<bool>>(
+    bit: B, msb: M, ctb: bool, values: &[u8; 256], pad: P, input: &[u8],
+) -> Result<(), DecodeError> {
+    if !pad.val() {
+        return validate_base(bit, msb, ctb, values, input);
+    }
+    let dec = dec(bit.val());
+    let mut inpos = 0;
+    while inpos < input.len() {
+        match validate_base(bit, msb, ctb, values, &input[inpos ..]) {
+            Ok(()) => break,
+            Err(error) => inpos += floor(error.position, dec),
+        }
+        let inlen = check_pad(bit, values, &input[inpos .. inpos + dec])
+            .map_err(|pos| DecodeError { position: inpos + pos, kind: DecodeKind::Padding })?;
+        validate_base(bit, msb, ctb, values, &input[inpos .. inpos + inlen]).map_err(
+            |mut error| {
+                error.position += inpos;
+                error
+            },
+        )?;
+        inpos += dec;
+    }
+    Ok(())
+}
+
+// Same as decode_wrap_block without output. Returns next input position.
+fn validate_wrap_block<B: Static<usize>, M: Static<bool>, P: Static<bool>>(
+    bit: B, msb: M, ctb: bool, values: &[u8; 256], pad: P, input: &[u8],
+) -> Result<usize, DecodeError> {
+    let mut buf = [0u8; 8];
+    let mut shift = [0usize; 8];
+    let (bufpos, inpos) = wrap_block(bit, values, input, &mut buf, &mut shift);
+    let shift = |mut error: DecodeError| {
+        error.position = shift[error.position];
+        error
+    };
+    let _ = decode_pad_len(bit, pad, bufpos).map_err(shift)?;
+    validate_pad(bit, msb, ctb, values, pad, &buf[.. bufpos]).map_err(shift)?;
+    Ok(inpos)
+}
+
+// Same as decode_wrap_mut without output.
+fn validate_wrap<B: Static<usize>, M: Static<bool>, P: Static<bool>, I: Static<bool>>(
+    bit: B, msb: M, ctb: bool, values: &[u8; 256], pad: P, has_ignore: I, input: &[u8],
+) -> Result<(), DecodeError> {
+    if !has_ignore.val() {
+        return validate_pad(bit, msb, ctb, values, pad, input);
+    }
+    let dec = dec(bit.val());
+    let mut inpos = 0;
+    while inpos < input.len() {
+        let (inlen, _) = decode_wrap_len(bit, pad, input.len() - inpos);
+        match validate_pad(bit, msb, ctb, values, pad, &input[inpos .. inpos + inlen]) {
+            Ok(()) => {
+                inpos += inlen;
+                break;
+            }
+            Err(error) => inpos += floor(error.position, dec),
+        }
+        inpos += validate_wrap_block(bit, msb, ctb, values, pad, &input[inpos ..]).map_err(
+            |mut error| {
+                error.position += inpos;
+                error
+            },
+        )?;
+    }
+    let inpos = skip_ignore(values, input, inpos);
+    check!(DecodeError { position: inpos, kind: DecodeKind::Length }, inpos == input.len());
+    Ok(())
+}
+
+// Returns `values[x]` without indexing `values` with `x`.
+#[cfg(feature = "alloc")]
+fn lookup_ct(values: &[u8; 256], x: u8) -> u8 {
+    let mut r = 0;
+    for (i, &v) in values.iter().enumerate() {
+        r |= v & zero_ct(i as u8 ^ x);
+    }
+    r
+}
+
+// Returns the all-ones mask if `x` is zero and the all-zeros mask otherwise, without branching.
+#[cfg(feature = "alloc")]
+fn zero_ct(x: u8) -> u8 {
+    (u32::from(x).wrapping_sub(1) >> 8) as u8
+}
+
 /// Order in which bits are read from a byte
 ///
 /// The base-conversion encoding is always little-endian. This means that the least significant
@@ -905,10 +1094,13 @@ pub type InternalEncoding = &'static [u8];
 //   0 - 256 (256) symbols
 // 256 - 512 (256) values
 // 512 - 513 (  1) padding
-// 513 - 514 (  1) reserved(3),ctb(1),msb(1),bit(3)
+// 513 - 514 (  1) reserved(2),prefix(1),ctb(1),msb(1),bit(3)
 // Optional fields:
 // 514 - 515 (  1) width
-// 515 -   * (  N) separator
+// 515 -   * (  N) separator (if prefix is false)
+// 515 - 516 (  1) prefix length P (if prefix is true)
+// 516 -   * (  P) prefix (if prefix is true)
+//   * -   * (  N) separator (if prefix is true)
 // Invariants:
 // - symbols is 2^bit unique characters repeated 2^(8-bit) times
 // - values[128 ..] are INVALID
@@ -920,6 +1112,7 @@ pub type InternalEncoding = &'static [u8];
 // - width is present if there is x such that values[x] is IGNORE
 // - width % dec(bit) == 0
 // - for all x in separator values[x] is IGNORE
+// - for all x in prefix values[x] is IGNORE
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct Encoding(#[doc(hidden)] pub InternalEncoding);
@@ -1182,7 +1375,8 @@ pub struct Wrap {
 /// assert_eq!(base64.encode(b"Hey you"), "SGV5IHlv dQ== ");
 /// ```
 ///
-/// Note that the output always ends with the separator.
+/// Note that the output always ends with the separator. Lines may also start with a prefix (e.g.
+/// for indentation) using [`Encoding::with_wrap_prefix()`].
 ///
 /// ### Translate characters when decoding
 ///
@@ -1206,6 +1400,10 @@ pub struct Specification {
     ///
     /// The number of symbols must be 2, 4, 8, 16, 32, or 64. Symbols must be ASCII characters
     /// (smaller than 128) and they must be unique.
+    ///
+    /// See [`unicode_encoding`] for symbols that are not ASCII characters.
+    ///
+    /// [`unicode_encoding`]: struct.Specification.html#method.unicode_encoding
     pub symbols: String,
 
     /// Bit-order
@@ -1286,16 +1484,21 @@ impl Encoding {
     fn block_len(&self) -> (usize, usize) {
         let bit = self.bit();
         match self.wrap() {
-            Some((col, end)) => (col / dec(bit) * enc(bit), col + end.len()),
+            Some((col, prefix, end)) => (col / dec(bit) * enc(bit), prefix.len() + col + end.len()),
             None => (enc(bit), dec(bit)),
         }
     }
 
-    fn wrap(&self) -> Option<(usize, &[u8])> {
+    fn wrap(&self) -> Option<(usize, &[u8], &[u8])> {
         if self.0.len() <= 515 {
             return None;
         }
-        Some((self.0[514] as usize, &self.0[515 ..]))
+        let col = self.0[514] as usize;
+        if self.0[513] & 0x20 == 0 {
+            return Some((col, &[], &self.0[515 ..]));
+        }
+        let (prefix, end) = self.0[516 ..].split_at(self.0[515] as usize);
+        Some((col, prefix, end))
     }
 
     fn has_ignore(&self) -> bool {
@@ -1329,7 +1532,7 @@ impl Encoding {
         dispatch! {
             let bit: usize = self.bit();
             let pad: Option<u8> = self.pad();
-            let wrap: Option<(usize, &[u8])> = self.wrap();
+            let wrap: Option<(usize, &[u8], &[u8])> = self.wrap();
             encode_wrap_len(bit, pad, wrap, len)
         }
     }
@@ -1344,7 +1547,7 @@ impl Encoding {
         let bit = self.bit();
         match self.wrap() {
             None => enc(bit),
-            Some((col, _)) => col * bit / 8,
+            Some((col, _, _)) => col * bit / 8,
         }
     }
 
@@ -1374,7 +1577,7 @@ impl Encoding {
             let bit: usize = self.bit();
             let msb: bool = self.msb();
             let pad: Option<u8> = self.pad();
-            let wrap: Option<(usize, &[u8])> = self.wrap();
+            let wrap: Option<(usize, &[u8], &[u8])> = self.wrap();
             encode_wrap_mut(bit, msb, self.sym(), pad, wrap, input, output)
         }
     }
@@ -1407,6 +1610,81 @@ impl Encoding {
         unsafe { core::str::from_utf8_unchecked(output) }
     }
 
+    /// Encodes the first `len` bytes of `buffer` in place and returns it as a `&str`
+    ///
+    /// The encoded output is the first [`encode_len(len)`] bytes of `buffer`. The rest of
+    /// `buffer` is not modified. The input is encoded from its end backwards, such that no separate
+    /// output buffer is needed. Wrapping is supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `buffer` length is smaller than the result of [`encode_len`] for `len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::BASE64;
+    /// let mut buffer = [0; 16];
+    /// buffer[.. 11].copy_from_slice(b"Hello world");
+    /// assert_eq!(BASE64.encode_in_place(&mut buffer, 11), "SGVsbG8gd29ybGQ=");
+    /// ```
+    ///
+    /// [`encode_len`]: struct.Encoding.html#method.encode_len
+    /// [`encode_len(len)`]: struct.Encoding.html#method.encode_len
+    pub fn encode_in_place<'a>(&self, buffer: &'a mut [u8], len: usize) -> &'a str {
+        const CHUNK: usize = 1024;
+        let olen = self.encode_len(len);
+        assert!(olen <= buffer.len());
+        // Encoding chunks which are multiple of the alignment (and start at such multiple) produce
+        // the same output as encoding the whole input. The alignment is at most 255 * 6 / 8, which
+        // is smaller than CHUNK.
+        let align = self.encode_align();
+        let chunk = align * core::cmp::max(1, CHUNK / align);
+        let mut input = [0u8; CHUNK];
+        let mut end = len;
+        let mut start = floor(len, chunk);
+        if start == len && len > 0 {
+            start -= chunk;
+        }
+        while start < end {
+            // The output of a chunk starts after its input start, so it only overwrites its own
+            // input (which we copy first) and not the input of the previous chunks.
+            let input = &mut input[.. end - start];
+            input.copy_from_slice(&buffer[start .. end]);
+            let output = &mut buffer[self.encode_len(start) .. self.encode_len(end)];
+            self.encode_mut(input, output);
+            end = start;
+            start = start.saturating_sub(chunk);
+        }
+        let output = &buffer[.. olen];
+        safety_assert!(output.is_ascii());
+        // SAFETY: Ensured by correctness guarantees of encode_mut (and asserted above).
+        unsafe { core::str::from_utf8_unchecked(output) }
+    }
+
+    /// Encodes `buffer` in place after resizing it
+    ///
+    /// This is a convenience wrapper around [`encode_in_place`] which resizes `buffer` to
+    /// [`encode_len`] of its length before encoding it. The encoded output is the whole `buffer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::BASE64_MIME;
+    /// let mut buffer = b"Hello world".to_vec();
+    /// assert_eq!(BASE64_MIME.encode_in_place_vec(&mut buffer), "SGVsbG8gd29ybGQ=\r\n");
+    /// assert_eq!(buffer, b"SGVsbG8gd29ybGQ=\r\n");
+    /// ```
+    ///
+    /// [`encode_in_place`]: struct.Encoding.html#method.encode_in_place
+    /// [`encode_len`]: struct.Encoding.html#method.encode_len
+    #[cfg(feature = "alloc")]
+    pub fn encode_in_place_vec<'a>(&self, buffer: &'a mut Vec<u8>) -> &'a str {
+        let len = buffer.len();
+        buffer.resize(self.encode_len(len), 0u8);
+        self.encode_in_place(buffer, len)
+    }
+
     /// Appends the encoding of `input` to `output`
     ///
     /// # Examples
@@ -1557,6 +1835,83 @@ impl Encoding {
         Ok(olen)
     }
 
+    /// Returns the exact decoded length of `input`
+    ///
+    /// Contrary to [`decode_len`] which only returns an upper bound when the encoding uses padding
+    /// or ignores characters, this function scans `input` and returns the exact length of the
+    /// output of [`decode`] (if it succeeds).
+    ///
+    /// Note that this function does not check whether symbols are valid or whether trailing bits
+    /// are zero: an input for which this function succeeds may still fail to decode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length cannot be computed. The error kind can be:
+    ///
+    /// - [`Length`] if the input length (without ignored characters) is invalid.
+    /// - [`Padding`] if the input has an invalid padding length.
+    ///
+    /// The error positions are the same as for [`decode`] (unless it would report an error of
+    /// another kind first).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::{BASE64, BASE64_MIME};
+    /// assert_eq!(BASE64.decode_len(16), Ok(12));
+    /// assert_eq!(BASE64.decoded_len_exact(b"SGVsbA==byB3b3JsZA=="), Ok(11));
+    /// assert_eq!(BASE64_MIME.decoded_len_exact(b"SGVsbG8gd29y\r\nbGQ=\r\n"), Ok(11));
+    /// assert_eq!(BASE64.decoded_len_exact(b"SGVsbA").unwrap_err().position, 4);
+    /// ```
+    ///
+    /// [`decode_len`]: struct.Encoding.html#method.decode_len
+    /// [`decode`]: struct.Encoding.html#method.decode
+    /// [`Length`]: enum.DecodeKind.html#variant.Length
+    /// [`Padding`]: enum.DecodeKind.html#variant.Padding
+    pub fn decoded_len_exact(&self, input: &[u8]) -> Result<usize, DecodeError> {
+        let olen = self.decode_len(input.len())?;
+        let has_pad = self.pad().is_some();
+        if !has_pad && !self.has_ignore() {
+            return Ok(olen);
+        }
+        let bit = self.bit();
+        let dec = dec(bit);
+        let values = self.val();
+        let mut len = 0;
+        // Position of the non-ignored characters of the current block.
+        let mut block = [0usize; 8];
+        let mut count = 0;
+        // Number of trailing padding characters of the current block.
+        let mut pads = 0;
+        for (i, &x) in input.iter().enumerate() {
+            let value = values[x as usize];
+            if value == IGNORE {
+                continue;
+            }
+            block[count] = i;
+            count += 1;
+            pads = if value == PADDING { pads + 1 } else { 0 };
+            if count < dec {
+                continue;
+            }
+            let ilen = dec - pads;
+            check!(
+                DecodeError { position: block[ilen], kind: DecodeKind::Padding },
+                pads == 0 || (ilen > 0 && bit * ilen % 8 < bit)
+            );
+            len += bit * ilen / 8;
+            count = 0;
+            pads = 0;
+        }
+        let (ilen, olen) = dispatch! {
+            let bit: usize = bit;
+            let pad: bool = has_pad;
+            decode_wrap_len(bit, pad, count)
+        };
+        check!(DecodeError { position: block[ilen], kind: DecodeKind::Length }, ilen == count);
+        Ok(len + olen)
+    }
+
     /// Decodes `input` in `output`
     ///
     /// Returns the length of the decoded output. This length may be smaller than the output length
@@ -1607,6 +1962,97 @@ impl Encoding {
         }
     }
 
+    /// Decodes `buffer` in place
+    ///
+    /// Returns the decoded output, which is a prefix of `buffer`. The bytes of `buffer` after the
+    /// decoded output are unspecified. This is useful to decode large inputs without allocating a
+    /// separate output, since the decoded output is never longer than its encoding. Ignored
+    /// characters and padding are supported like in [`decode_mut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `buffer` is invalid. See [`decode`] for more details. The [`read`] first
+    /// bytes of the input have been successfully decoded to the [`written`] first bytes of
+    /// `buffer`. The rest of `buffer` is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::BASE64_MIME;
+    /// let mut buffer = b"SGVsbG8g\r\nd29ybGQ=\r\n".to_vec();
+    /// let output = BASE64_MIME.decode_in_place(&mut buffer).unwrap();
+    /// assert_eq!(output, b"Hello world");
+    /// ```
+    ///
+    /// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+    /// [`decode`]: struct.Encoding.html#method.decode
+    /// [`read`]: struct.DecodePartial.html#structfield.read
+    /// [`written`]: struct.DecodePartial.html#structfield.written
+    pub fn decode_in_place<'a>(&self, buffer: &'a mut [u8]) -> Result<&'a mut [u8], DecodePartial> {
+        // Must be a multiple of dec(bit) for all bit such that chunks start at block boundaries.
+        const CHUNK: usize = 512;
+        let _ = self.decode_len(buffer.len()).map_err(|error| DecodePartial {
+            read: 0,
+            written: 0,
+            error,
+        })?;
+        let has_ignore = self.has_ignore();
+        let values = self.val();
+        let mut input = [0u8; CHUNK];
+        let mut output = [0u8; CHUNK];
+        // Position in buffer of each character in input (only used with ignored characters).
+        let mut shift = [0usize; CHUNK];
+        let mut inpos = 0;
+        let mut outpos = 0;
+        loop {
+            // We copy the next characters (skipping ignored ones) to a separate input, such that
+            // decoding can overwrite them in buffer.
+            let start = inpos;
+            let mut len = 0;
+            if has_ignore {
+                while len < CHUNK && inpos < buffer.len() {
+                    if values[buffer[inpos] as usize] != IGNORE {
+                        input[len] = buffer[inpos];
+                        shift[len] = inpos;
+                        len += 1;
+                    }
+                    inpos += 1;
+                }
+            } else {
+                len = core::cmp::min(CHUNK, buffer.len() - inpos);
+                input[.. len].copy_from_slice(&buffer[inpos .. inpos + len]);
+                inpos += len;
+            }
+            let position = |pos: usize| match pos {
+                _ if pos == len => inpos,
+                _ if has_ignore => shift[pos],
+                _ => start + pos,
+            };
+            let olen = self.decode_len(len).unwrap();
+            let (written, result) = match self.decode_mut(&input[.. len], &mut output[.. olen]) {
+                Ok(written) => (written, Ok(())),
+                Err(partial) => (partial.written, Err(partial)),
+            };
+            // The decoded output is not longer than the characters we already copied.
+            debug_assert!(outpos + written <= inpos);
+            buffer[outpos .. outpos + written].copy_from_slice(&output[.. written]);
+            outpos += written;
+            if let Err(partial) = result {
+                return Err(DecodePartial {
+                    read: position(partial.read),
+                    written: outpos,
+                    error: DecodeError {
+                        position: position(partial.error.position),
+                        kind: partial.error.kind,
+                    },
+                });
+            }
+            if inpos == buffer.len() {
+                return Ok(&mut buffer[.. outpos]);
+            }
+        }
+    }
+
     /// Returns decoded `input`
     ///
     /// # Errors
@@ -1644,65 +2090,669 @@ impl Encoding {
         Ok(output)
     }
 
-    /// Returns the bit-width
-    #[must_use]
-    pub fn bit_width(&self) -> usize {
-        self.bit()
+    /// Checks whether `input` is valid
+    ///
+    /// This function does the same checks as [`decode`] and returns the same error, but it doesn't
+    /// produce any output. It is thus faster than decoding and doesn't need a buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. See [`decode`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::{DecodeError, DecodeKind, BASE64, HEXLOWER};
+    /// assert_eq!(BASE64.validate(b"SGVsbA==byB3b3JsZA=="), Ok(()));
+    /// assert_eq!(BASE64.validate(b"SGVsbA=="), Ok(()));
+    /// assert_eq!(BASE64.validate(b"SGVsbB=="), Err(DecodeError {
+    ///     position: 5,
+    ///     kind: DecodeKind::Trailing
+    /// }));
+    /// assert_eq!(HEXLOWER.validate(b"deadBEEF").unwrap_err().position, 4);
+    /// ```
+    ///
+    /// [`decode`]: struct.Encoding.html#method.decode
+    pub fn validate(&self, input: &[u8]) -> Result<(), DecodeError> {
+        let _ = self.decode_len(input.len())?;
+        dispatch! {
+            let bit: usize = self.bit();
+            let msb: bool = self.msb();
+            let pad: bool = self.pad().is_some();
+            let has_ignore: bool = self.has_ignore();
+            validate_wrap(bit, msb, self.ctb(), self.val(), pad, has_ignore, input)
+        }
     }
 
-    /// Interprets a byte as a character
-    pub fn interpret_byte(&self, byte: u8) -> Character {
-        match self.val()[byte as usize] {
-            INVALID => Character::Invalid,
-            IGNORE => Character::Ignored,
-            PADDING => Character::Padding,
-            value => Character::Symbol { value: value as usize },
-        }
+    /// Returns decoded `input` and all its errors
+    ///
+    /// Contrary to [`decode`] which stops at the first error, this function skips the block
+    /// containing an error and resumes decoding at the next block. The output is thus the
+    /// concatenation of the successfully decoded blocks. The errors are in increasing position
+    /// order and have the same kind and position as described in [`decode`]. An invalid length is
+    /// reported last (with the greatest valid input length as position) and the characters after
+    /// this length are not decoded.
+    ///
+    /// The input is valid if and only if the returned errors are empty, in which case the output
+    /// is the same as [`decode`]. See [`decode_diagnose_limit`] to bound the number of reported
+    /// errors for untrusted inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::{DecodeError, DecodeKind, BASE64};
+    /// let (output, errors) = BASE64.decode_diagnose(b"SGVs!G8gd2*ybGQ=");
+    /// assert_eq!(output, b"Helld");
+    /// assert_eq!(errors, [
+    ///     DecodeError { position: 4, kind: DecodeKind::Symbol },
+    ///     DecodeError { position: 10, kind: DecodeKind::Symbol },
+    /// ]);
+    /// ```
+    ///
+    /// [`decode`]: struct.Encoding.html#method.decode
+    /// [`decode_diagnose_limit`]: struct.Encoding.html#method.decode_diagnose_limit
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn decode_diagnose(&self, input: &[u8]) -> (Vec<u8>, Vec<DecodeError>) {
+        self.decode_diagnose_until(input, usize::MAX)
     }
 
-    /// Returns whether the encoding is canonical
+    /// Returns decoded `input` and its first `limit` errors
     ///
-    /// An encoding is not canonical if one of the following conditions holds:
+    /// This function behaves like [`decode_diagnose`] but stops decoding after `limit` errors. The
+    /// output is the concatenation of the blocks successfully decoded before the last reported
+    /// error.
     ///
-    /// - trailing bits are not checked
-    /// - padding is used
-    /// - characters are ignored
-    /// - characters are translated
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::num::NonZeroUsize;
+    /// use data_encoding::{DecodeError, DecodeKind, BASE64};
+    /// let limit = NonZeroUsize::new(1).unwrap();
+    /// let (output, errors) = BASE64.decode_diagnose_limit(b"SGVs!G8gd2*ybGQ=", limit);
+    /// assert_eq!(output, b"Hel");
+    /// assert_eq!(errors, [DecodeError { position: 4, kind: DecodeKind::Symbol }]);
+    /// ```
+    ///
+    /// [`decode_diagnose`]: struct.Encoding.html#method.decode_diagnose
+    #[cfg(feature = "alloc")]
     #[must_use]
-    pub fn is_canonical(&self) -> bool {
-        if !self.ctb() {
-            return false;
-        }
-        let bit = self.bit();
-        let sym = self.sym();
-        let val = self.val();
-        for i in 0 .. 256 {
-            if val[i] == INVALID {
-                continue;
+    pub fn decode_diagnose_limit(
+        &self, input: &[u8], limit: core::num::NonZeroUsize,
+    ) -> (Vec<u8>, Vec<DecodeError>) {
+        self.decode_diagnose_until(input, limit.get())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decode_diagnose_until(&self, input: &[u8], limit: usize) -> (Vec<u8>, Vec<DecodeError>) {
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        let (input, length) = match self.decode_len(input.len()) {
+            Ok(_) => (input, None),
+            Err(error) => (&input[.. error.position], Some(error)),
+        };
+        let dec = dec(self.bit());
+        let mut inpos = 0;
+        let mut outpos = 0;
+        while inpos < input.len() {
+            // The buffer only grows, such that the total work stays linear in the input length.
+            let len = self.decode_len(input.len() - inpos).unwrap();
+            if output.len() < outpos + len {
+                output.resize(outpos + len, 0);
             }
-            if val[i] >= 1 << bit {
-                return false;
+            let partial =
+                match self.decode_mut(&input[inpos ..], &mut output[outpos .. outpos + len]) {
+                    Ok(written) => {
+                        outpos += written;
+                        break;
+                    }
+                    Err(partial) => partial,
+                };
+            outpos += partial.written;
+            errors.push(DecodeError {
+                position: inpos + partial.error.position,
+                kind: partial.error.kind,
+            });
+            if partial.error.kind == DecodeKind::Length || errors.len() == limit {
+                break;
             }
-            if sym[val[i] as usize] as usize != i {
-                return false;
+            // We skip the block containing the error.
+            inpos += partial.read;
+            let mut count = 0;
+            while inpos < input.len() && count < dec {
+                if self.val()[input[inpos] as usize] != IGNORE {
+                    count += 1;
+                }
+                inpos += 1;
             }
         }
-        true
+        output.truncate(outpos);
+        if errors.len() < limit {
+            errors.extend(length);
+        }
+        (output, errors)
     }
 
-    /// Returns the encoding specification
-    #[allow(clippy::missing_panics_doc)] // no panic
-    #[cfg(feature = "alloc")]
-    #[must_use]
-    pub fn specification(&self) -> Specification {
-        let mut specification = Specification::new();
-        specification
-            .symbols
-            .push_str(core::str::from_utf8(&self.sym()[0 .. 1 << self.bit()]).unwrap());
-        specification.bit_order =
-            if self.msb() { MostSignificantFirst } else { LeastSignificantFirst };
-        specification.check_trailing_bits = self.ctb();
-        if let Some(pad) = self.pad() {
+    /// Returns decoded `input` in constant-time
+    ///
+    /// This function returns the same result as [`decode`] but its execution
+    /// time does not depend on the value of the symbols in the input: it neither indexes tables
+    /// with input characters nor branches on decoded values. It is meant to decode secrets like
+    /// private keys or tokens, and is slower than [`decode`].
+    ///
+    /// The execution time may still depend on the input length and on the position of the
+    /// characters which are not symbols (padding, ignored, and invalid characters), which are thus
+    /// considered public.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. See [`decode`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::BASE64;
+    /// assert_eq!(BASE64.decode_ct(b"SGVsbA==byB3b3JsZA==").unwrap(), b"Hello world");
+    /// ```
+    ///
+    /// [`decode`]: struct.Encoding.html#method.decode
+    #[cfg(feature = "alloc")]
+    pub fn decode_ct(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let bit = self.bit();
+        let values = self.val();
+        // We first check the structure of the input by replacing all symbols with the same one.
+        // This reports all errors but trailing bits errors.
+        let symbol = self.sym()[0];
+        let skeleton: Vec<u8> = input
+            .iter()
+            .map(|&x| {
+                let mask = zero_ct(lookup_ct(values, x) >> bit);
+                x & !mask | symbol & mask
+            })
+            .collect();
+        let result = self.decode(&skeleton);
+        // We then decode the symbols. Padding characters end a block. Trailing bits errors are
+        // only reported if the block ends before the structural error (if any), because decoding
+        // stops at the first error.
+        let error = result.as_ref().err().copied();
+        let limit = match error {
+            None => usize::MAX,
+            Some(e) if e.kind == DecodeKind::Length && !self.has_ignore() => 0,
+            Some(e) => e.position,
+        };
+        let mut output = result.unwrap_or_default();
+        let mut acc = 0u64;
+        let mut len = 0;
+        let mut outpos = 0;
+        let mut last = 0;
+        let mut trailing = 0u8;
+        let mut position = 0;
+        let mut check = |acc: u64, len: usize, last: usize, end: usize| {
+            if !self.ctb() || len == 0 || limit <= end {
+                return;
+            }
+            let bad = !zero_ct(if self.msb() { acc & ((1 << len) - 1) } else { acc } as u8);
+            position |= last & usize::from(bad & !trailing & 1).wrapping_neg();
+            trailing |= bad;
+        };
+        for (i, &x) in input.iter().enumerate() {
+            let value = lookup_ct(values, x);
+            if skeleton[i] != symbol {
+                if value == PADDING {
+                    check(acc, len, last, i);
+                    acc = 0;
+                    len = 0;
+                }
+                continue;
+            }
+            last = i;
+            if self.msb() {
+                acc = acc << bit | u64::from(value);
+            } else {
+                acc |= u64::from(value) << len;
+            }
+            len += bit;
+            if len >= 8 {
+                len -= 8;
+                if let Some(output) = output.get_mut(outpos) {
+                    *output = if self.msb() { (acc >> len) as u8 } else { acc as u8 };
+                }
+                if !self.msb() {
+                    acc >>= 8;
+                }
+                outpos += 1;
+            }
+        }
+        check(acc, len, last, input.len());
+        if trailing != 0 {
+            return Err(DecodeError { position, kind: DecodeKind::Trailing });
+        }
+        if let Some(error) = error {
+            return Err(error);
+        }
+        debug_assert_eq!(outpos, output.len());
+        Ok(output)
+    }
+
+    /// Returns an object to decode a fragmented input and append it to `output`
+    ///
+    /// See the documentation of [`Decoder`] for more details and examples.
+    #[cfg(feature = "alloc")]
+    pub fn new_decoder<'a>(&'a self, output: &'a mut Vec<u8>) -> Decoder<'a> {
+        Decoder::new(self, output)
+    }
+
+    /// Returns a reader encoding the data read from `reader`
+    ///
+    /// See the documentation of [`EncodeReader`] for more details and examples.
+    #[cfg(feature = "std")]
+    pub fn new_encode_reader<R: std::io::Read>(&self, reader: R) -> EncodeReader<'_, R> {
+        EncodeReader::new(self, reader)
+    }
+
+    /// Returns a reader decoding the data read from `reader`
+    ///
+    /// See the documentation of [`DecodeReader`] for more details and examples.
+    #[cfg(feature = "std")]
+    pub fn new_decode_reader<R: std::io::Read>(&self, reader: R) -> DecodeReader<'_, R> {
+        DecodeReader::new(self, reader)
+    }
+
+    /// Returns a writer encoding the data written to it into `writer`
+    ///
+    /// See the documentation of [`EncodeWriter`] for more details and examples.
+    #[cfg(feature = "std")]
+    pub fn new_encode_writer<W: std::io::Write>(&self, writer: W) -> EncodeWriter<'_, W> {
+        EncodeWriter::new(self, writer)
+    }
+
+    /// Returns a writer decoding the data written to it into `writer`
+    ///
+    /// See the documentation of [`DecodeWriter`] for more details and examples.
+    #[cfg(feature = "std")]
+    pub fn new_decode_writer<W: std::io::Write>(&self, writer: W) -> DecodeWriter<'_, W> {
+        DecodeWriter::new(self, writer)
+    }
+
+    /// Returns an asynchronous reader encoding the data read from `reader`
+    ///
+    /// See the documentation of [`AsyncEncodeReader`] for more details.
+    ///
+    /// [`AsyncEncodeReader`]: tokio/struct.AsyncEncodeReader.html
+    #[cfg(all(feature = "std", feature = "tokio"))]
+    pub fn new_async_encode_reader<R: ::tokio::io::AsyncRead + Unpin>(
+        &self, reader: R,
+    ) -> crate::tokio::AsyncEncodeReader<'_, R> {
+        crate::tokio::AsyncEncodeReader::new(self, reader)
+    }
+
+    /// Returns an asynchronous reader decoding the data read from `reader`
+    ///
+    /// See the documentation of [`AsyncDecodeReader`] for more details.
+    ///
+    /// [`AsyncDecodeReader`]: tokio/struct.AsyncDecodeReader.html
+    #[cfg(all(feature = "std", feature = "tokio"))]
+    pub fn new_async_decode_reader<R: ::tokio::io::AsyncRead + Unpin>(
+        &self, reader: R,
+    ) -> crate::tokio::AsyncDecodeReader<'_, R> {
+        crate::tokio::AsyncDecodeReader::new(self, reader)
+    }
+
+    /// Returns an asynchronous writer encoding the data written to it into `writer`
+    ///
+    /// See the documentation of [`AsyncEncodeWriter`] for more details.
+    ///
+    /// [`AsyncEncodeWriter`]: tokio/struct.AsyncEncodeWriter.html
+    #[cfg(all(feature = "std", feature = "tokio"))]
+    pub fn new_async_encode_writer<W: ::tokio::io::AsyncWrite + Unpin>(
+        &self, writer: W,
+    ) -> crate::tokio::AsyncEncodeWriter<'_, W> {
+        crate::tokio::AsyncEncodeWriter::new(self, writer)
+    }
+
+    /// Returns an asynchronous writer decoding the data written to it into `writer`
+    ///
+    /// See the documentation of [`AsyncDecodeWriter`] for more details.
+    ///
+    /// [`AsyncDecodeWriter`]: tokio/struct.AsyncDecodeWriter.html
+    #[cfg(all(feature = "std", feature = "tokio"))]
+    pub fn new_async_decode_writer<W: ::tokio::io::AsyncWrite + Unpin>(
+        &self, writer: W,
+    ) -> crate::tokio::AsyncDecodeWriter<'_, W> {
+        crate::tokio::AsyncDecodeWriter::new(self, writer)
+    }
+
+    /// Encodes `input` and writes the result to `output`
+    ///
+    /// This is equivalent to encoding the concatenation of all the chunks of `input`. In
+    /// particular, blocks may be split across chunks, so a chain of buffers doesn't need to be
+    /// flattened first. The input is encoded by pieces, so no memory proportional to its length is
+    /// needed (except for the output).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` doesn't have enough remaining capacity (see [`BufMut::put_slice()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::{Buf, BytesMut};
+    /// use data_encoding::BASE64;
+    /// let input = (&b"Hello "[..]).chain(&b"world"[..]);
+    /// let mut output = BytesMut::new();
+    /// BASE64.encode_buf(input, &mut output);
+    /// assert_eq!(output, "SGVsbG8gd29ybGQ=");
+    /// ```
+    ///
+    /// [`BufMut::put_slice()`]: bytes::BufMut::put_slice
+    #[cfg(all(feature = "alloc", feature = "bytes"))]
+    pub fn encode_buf(&self, mut input: impl bytes::Buf, output: &mut impl bytes::BufMut) {
+        let align = self.encode_align();
+        // The last incomplete block and the encoded output of the current piece.
+        let mut block = Vec::with_capacity(align);
+        let mut encoded = Vec::new();
+        let mut put = |input: &[u8], output: &mut dyn bytes::BufMut| {
+            encoded.resize(self.encode_len(input.len()), 0);
+            self.encode_mut(input, &mut encoded);
+            output.put_slice(&encoded);
+        };
+        while input.has_remaining() {
+            let chunk = input.chunk();
+            let len = core::cmp::min(chunk.len(), BUF_PIECE);
+            if !block.is_empty() {
+                let len = core::cmp::min(align - block.len(), len);
+                block.extend_from_slice(&chunk[.. len]);
+                input.advance(len);
+                if block.len() == align {
+                    put(&block, output);
+                    block.clear();
+                }
+                continue;
+            }
+            let aligned = floor(len, align);
+            put(&chunk[.. aligned], output);
+            block.extend_from_slice(&chunk[aligned .. len]);
+            input.advance(len);
+        }
+        put(&block, output);
+    }
+
+    /// Decodes `input` and writes the result to `output`
+    ///
+    /// Returns the number of bytes written to `output`.
+    ///
+    /// This is equivalent to decoding the concatenation of all the chunks of `input`. In
+    /// particular, blocks, padding, and ignored characters may be split across chunks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. See [`decode`] for more details. The error position
+    /// is relative to the concatenation of all chunks. The data decoded before the error position
+    /// may have been written to `output` (at most up to the block containing the error).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` doesn't have enough remaining capacity (see [`BufMut::put_slice()`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::{Buf, BytesMut};
+    /// use data_encoding::BASE64_MIME;
+    /// let input = (&b"SGVsbG8g\r"[..]).chain(&b"\nd29ybGQ="[..]);
+    /// let mut output = BytesMut::new();
+    /// assert_eq!(BASE64_MIME.decode_buf(input, &mut output), Ok(11));
+    /// assert_eq!(output, "Hello world");
+    /// ```
+    ///
+    /// [`BufMut::put_slice()`]: bytes::BufMut::put_slice
+    /// [`decode`]: struct.Encoding.html#method.decode
+    #[cfg(all(feature = "alloc", feature = "bytes"))]
+    pub fn decode_buf(
+        &self, mut input: impl bytes::Buf, output: &mut impl bytes::BufMut,
+    ) -> Result<usize, DecodeError> {
+        let mut state = DecodeState::new();
+        let mut decoded = Vec::new();
+        let mut written = 0;
+        let mut put = |state: &mut DecodeState, len: usize, output: &mut dyn bytes::BufMut| {
+            decoded.clear();
+            let result = state.decode(self, len, &mut decoded);
+            output.put_slice(&decoded);
+            written += decoded.len();
+            result
+        };
+        while input.has_remaining() {
+            let chunk = input.chunk();
+            let len = core::cmp::min(chunk.len(), BUF_PIECE);
+            state.buffer.extend_from_slice(&chunk[.. len]);
+            input.advance(len);
+            let len = state.complete_len(self);
+            put(&mut state, len, output)?;
+        }
+        let len = state.buffer.len();
+        put(&mut state, len, output)?;
+        Ok(written)
+    }
+
+    /// Encodes `input` in `output` in parallel
+    ///
+    /// This is the parallel version of [`encode_mut`] and produces the same output. The input is
+    /// split in pieces aligned to [`encode_align`] (i.e. on block or wrapping line boundaries)
+    /// which are encoded in parallel with [rayon].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of [`encode_len`] for the `input`
+    /// length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::BASE64;
+    /// let input = vec![0x5a; 1 << 20];
+    /// let mut output = vec![0; BASE64.encode_len(input.len())];
+    /// BASE64.par_encode_mut(&input, &mut output);
+    /// assert_eq!(output, BASE64.encode(&input).as_bytes());
+    /// ```
+    ///
+    /// [`encode_align`]: struct.Encoding.html#method.encode_align
+    /// [`encode_len`]: struct.Encoding.html#method.encode_len
+    /// [`encode_mut`]: struct.Encoding.html#method.encode_mut
+    /// [rayon]: https://docs.rs/rayon
+    #[cfg(all(feature = "std", feature = "rayon"))]
+    pub fn par_encode_mut(&self, input: &[u8], output: &mut [u8]) {
+        use rayon::prelude::*;
+        assert_eq!(output.len(), self.encode_len(input.len()));
+        let align = self.encode_align();
+        let ilen = core::cmp::max(floor(PAR_PIECE, align), align);
+        let olen = self.encode_len(ilen);
+        input
+            .par_chunks(ilen)
+            .zip(output.par_chunks_mut(olen))
+            .for_each(|(input, output)| self.encode_mut(input, output));
+    }
+
+    /// Decodes `input` in `output` in parallel
+    ///
+    /// This is the parallel version of [`decode_mut`] and returns the same result (including the
+    /// error with the smallest position if `input` is invalid). The input is split in pieces
+    /// aligned to blocks (taking ignored characters into account) which are decoded in parallel
+    /// with [rayon].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `output` length does not match the result of [`decode_len`] for the `input`
+    /// length. Also panics if `decode_len` fails for the `input` length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. See [`decode_mut`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::BASE64_MIME;
+    /// let input = BASE64_MIME.encode(&vec![0x5a; 1 << 20]);
+    /// let mut output = vec![0; BASE64_MIME.decode_len(input.len()).unwrap()];
+    /// let len = BASE64_MIME.par_decode_mut(input.as_bytes(), &mut output).unwrap();
+    /// assert_eq!(output[.. len], vec![0x5a; 1 << 20]);
+    /// ```
+    ///
+    /// [`decode_len`]: struct.Encoding.html#method.decode_len
+    /// [`decode_mut`]: struct.Encoding.html#method.decode_mut
+    /// [rayon]: https://docs.rs/rayon
+    #[cfg(all(feature = "std", feature = "rayon"))]
+    pub fn par_decode_mut(&self, input: &[u8], output: &mut [u8]) -> Result<usize, DecodePartial> {
+        use rayon::prelude::*;
+        assert_eq!(Ok(output.len()), self.decode_len(input.len()));
+        let bounds = self.par_decode_bounds(input);
+        // Split the input and output in pieces (with their input and output offsets).
+        let mut pieces = Vec::with_capacity(bounds.len());
+        let mut rest = &mut output[..];
+        let mut ipos = 0;
+        let mut opos = 0;
+        for &end in &bounds {
+            let olen = self.decode_len(end - ipos).unwrap();
+            let (piece, next) = rest.split_at_mut(olen);
+            pieces.push((ipos, opos, &input[ipos .. end], piece));
+            rest = next;
+            ipos = end;
+            opos += olen;
+        }
+        let results: Vec<_> = pieces
+            .into_par_iter()
+            .map(|(ipos, opos, input, output)| (ipos, opos, self.decode_mut(input, output)))
+            .collect();
+        // Move the decoded pieces next to each other (they may be shorter than their output).
+        let mut written = 0;
+        for (ipos, opos, result) in results {
+            let len = match result {
+                Ok(len) => len,
+                Err(partial) => partial.written,
+            };
+            output.copy_within(opos .. opos + len, written);
+            written += len;
+            if let Err(mut partial) = result {
+                partial.read += ipos;
+                partial.written = written;
+                partial.error.position += ipos;
+                return Err(partial);
+            }
+        }
+        Ok(written)
+    }
+
+    // Returns the end of each piece of input for parallel decoding.
+    //
+    // Pieces start at block boundaries, such that they can be decoded independently.
+    #[cfg(all(feature = "std", feature = "rayon"))]
+    fn par_decode_bounds(&self, input: &[u8]) -> Vec<usize> {
+        use rayon::prelude::*;
+        let dec = dec(self.bit());
+        let len = input.len();
+        if !self.has_ignore() {
+            let ilen = floor(PAR_PIECE, dec);
+            return (1 ..= len / ilen)
+                .map(|i| i * ilen)
+                .filter(|&x| x < len)
+                .chain(Some(len))
+                .collect();
+        }
+        // The number of non-ignored characters of each piece tells how far to extend each piece
+        // until the next block boundary.
+        let values = self.val();
+        let counts: Vec<usize> = input
+            .par_chunks(PAR_PIECE)
+            .map(|piece| piece.iter().filter(|&&x| values[x as usize] != IGNORE).count())
+            .collect();
+        let mut bounds = Vec::with_capacity(counts.len());
+        // Number of non-ignored characters before the current piece boundary.
+        let mut prefix = 0;
+        for (i, count) in counts.into_iter().enumerate() {
+            prefix += count;
+            let mut pos = core::cmp::min((i + 1) * PAR_PIECE, len);
+            if bounds.last().map_or(false, |&end| pos <= end) {
+                continue;
+            }
+            let mut count = prefix;
+            while count % dec != 0 && pos < len {
+                if values[input[pos] as usize] != IGNORE {
+                    count += 1;
+                }
+                pos += 1;
+            }
+            bounds.push(pos);
+        }
+        if bounds.is_empty() {
+            bounds.push(len);
+        }
+        bounds
+    }
+
+    /// Returns the bit-width
+    #[must_use]
+    pub fn bit_width(&self) -> usize {
+        self.bit()
+    }
+
+    /// Interprets a byte as a character
+    pub fn interpret_byte(&self, byte: u8) -> Character {
+        match self.val()[byte as usize] {
+            INVALID => Character::Invalid,
+            IGNORE => Character::Ignored,
+            PADDING => Character::Padding,
+            value => Character::Symbol { value: value as usize },
+        }
+    }
+
+    /// Returns whether the encoding is canonical
+    ///
+    /// An encoding is not canonical if one of the following conditions holds:
+    ///
+    /// - trailing bits are not checked
+    /// - padding is used
+    /// - characters are ignored
+    /// - characters are translated
+    #[must_use]
+    pub fn is_canonical(&self) -> bool {
+        if !self.ctb() {
+            return false;
+        }
+        let bit = self.bit();
+        let sym = self.sym();
+        let val = self.val();
+        for i in 0 .. 256 {
+            if val[i] == INVALID {
+                continue;
+            }
+            if val[i] >= 1 << bit {
+                return false;
+            }
+            if sym[val[i] as usize] as usize != i {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the encoding specification
+    ///
+    /// The [wrapping prefix] is not part of the specification. Its characters are ignored like the
+    /// wrapping separator.
+    ///
+    /// [wrapping prefix]: struct.Encoding.html#method.with_wrap_prefix
+    #[allow(clippy::missing_panics_doc)] // no panic
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn specification(&self) -> Specification {
+        let mut specification = Specification::new();
+        specification
+            .symbols
+            .push_str(core::str::from_utf8(&self.sym()[0 .. 1 << self.bit()]).unwrap());
+        specification.bit_order =
+            if self.msb() { MostSignificantFirst } else { LeastSignificantFirst };
+        specification.check_trailing_bits = self.ctb();
+        if let Some(pad) = self.pad() {
             specification.padding = Some(pad as char);
         }
         for i in 0 .. 128u8 {
@@ -1711,7 +2761,7 @@ impl Encoding {
             }
             specification.ignore.push(i as char);
         }
-        if let Some((col, end)) = self.wrap() {
+        if let Some((col, _, end)) = self.wrap() {
             specification.wrap.width = col;
             specification.wrap.separator = core::str::from_utf8(end).unwrap().to_owned();
         }
@@ -1732,6 +2782,32 @@ impl Encoding {
         specification
     }
 
+    /// Returns the same encoding with a prefix at the beginning of each wrapped line
+    ///
+    /// The prefix (e.g. indentation) is not counted in the wrapping width and replaces any previous
+    /// prefix. Its characters are ignored when decoding. The prefix is ignored if wrapping is
+    /// disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prefix is too long or if one of its characters is already a symbol,
+    /// the padding, or a translated character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut spec = data_encoding::BASE64.specification();
+    /// spec.wrap.width = 8;
+    /// spec.wrap.separator.push_str("\n");
+    /// let base64 = spec.encoding().unwrap().with_wrap_prefix("  ").unwrap();
+    /// assert_eq!(base64.encode(b"Hey you"), "  SGV5IHlv\n  dQ==\n");
+    /// assert_eq!(base64.decode(b"  SGV5IHlv\n  dQ==\n").unwrap(), b"Hey you");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_wrap_prefix(&self, prefix: &str) -> Result<Encoding, SpecificationError> {
+        self.specification().encoding_with_prefix(prefix.as_bytes())
+    }
+
     #[doc(hidden)]
     #[must_use]
     pub const fn internal_new(implementation: &'static [u8]) -> Encoding {
@@ -1826,6 +2902,525 @@ impl<'a> Encoder<'a> {
     pub fn finalize(self) {}
 }
 
+/// Decodes fragmented input to an output
+///
+/// It is equivalent to use a [`Decoder`] with multiple calls to [`Decoder::append()`] followed by
+/// [`Decoder::finalize()`] than to first concatenate all the input and then use
+/// [`Encoding::decode()`]. In particular, blocks, padding, and ignored characters may be split
+/// across fragments, and error positions are relative to the concatenated input.
+///
+/// After an error, the output contains the data successfully decoded before the error position,
+/// and the decoder should not be used anymore.
+///
+/// # Examples
+///
+/// ```rust
+/// // See the documentation of Encoder for why we need a static.
+/// static BASE64: data_encoding::Encoding = data_encoding::BASE64;
+/// let mut output = Vec::new();
+/// let mut decoder = BASE64.new_decoder(&mut output);
+/// decoder.append(b"aGVsbG8gd2").unwrap();
+/// decoder.append(b"9ybGQ=").unwrap();
+/// decoder.finalize().unwrap();
+/// assert_eq!(output, b"hello world");
+/// ```
+#[derive(Debug)]
+#[cfg(feature = "alloc")]
+pub struct Decoder<'a> {
+    encoding: &'a Encoding,
+    output: &'a mut Vec<u8>,
+    state: DecodeState,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Decoder<'a> {
+    fn new(encoding: &'a Encoding, output: &'a mut Vec<u8>) -> Self {
+        Decoder { encoding, output, state: DecodeState::new() }
+    }
+
+    /// Decodes the provided input fragment and appends the result to the output
+    ///
+    /// Only complete blocks are decoded. The rest of the input is kept until the next call to
+    /// [`Decoder::append()`] or [`Decoder::finalize()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is invalid. See [`Encoding::decode()`] for more details. The
+    /// error position is relative to the concatenation of all fragments. Errors for incomplete
+    /// blocks are only returned when the block is completed or the decoder finalized.
+    pub fn append(&mut self, input: &[u8]) -> Result<(), DecodeError> {
+        self.state.buffer.extend_from_slice(input);
+        let len = self.state.complete_len(self.encoding);
+        self.state.decode(self.encoding, len, self.output)
+    }
+
+    /// Makes sure all inputs have been decoded and appended to the output
+    ///
+    /// Contrary to [`Encoder::finalize()`], this is not equivalent to dropping the decoder because
+    /// the last block may be invalid. Dropping the decoder discards the last incomplete block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the last block is invalid. See [`Decoder::append()`] for more details.
+    pub fn finalize(mut self) -> Result<(), DecodeError> {
+        let len = self.state.buffer.len();
+        self.state.decode(self.encoding, len, self.output)
+    }
+}
+
+// Maximum length of input processed at once by `Encoding::{encode,decode}_buf()`.
+#[cfg(all(feature = "alloc", feature = "bytes"))]
+const BUF_PIECE: usize = 4096;
+
+// Length of input processed by each task of `Encoding::par_{encode,decode}_mut()`.
+#[cfg(all(feature = "std", feature = "rayon"))]
+const PAR_PIECE: usize = 1 << 16;
+
+// Input that has not been decoded yet, and its position in the concatenated input.
+#[derive(Debug)]
+#[cfg(feature = "alloc")]
+struct DecodeState {
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl DecodeState {
+    fn new() -> Self {
+        DecodeState { buffer: Vec::new(), position: 0 }
+    }
+
+    // Returns the length of the longest buffer prefix made of complete blocks (and the ignored
+    // characters following them).
+    fn complete_len(&self, encoding: &Encoding) -> usize {
+        let dec = dec(encoding.bit());
+        if !encoding.has_ignore() {
+            return floor(self.buffer.len(), dec);
+        }
+        let values = encoding.val();
+        let mut count = 0;
+        let mut len = 0;
+        for (i, &x) in self.buffer.iter().enumerate() {
+            if values[x as usize] == IGNORE {
+                if count == 0 {
+                    len = i + 1;
+                }
+                continue;
+            }
+            count = (count + 1) % dec;
+            if count == 0 {
+                len = i + 1;
+            }
+        }
+        len
+    }
+
+    // Decodes the first `len` bytes of the buffer and appends the result to `output`.
+    fn decode(
+        &mut self, encoding: &Encoding, len: usize, output: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        let position = self.position;
+        let shift = |mut error: DecodeError| {
+            error.position += position;
+            error
+        };
+        let olen = encoding.decode_len(len).map_err(shift)?;
+        let start = output.len();
+        output.resize(start + olen, 0);
+        match encoding.decode_mut(&self.buffer[.. len], &mut output[start ..]) {
+            Ok(written) => output.truncate(start + written),
+            Err(partial) => {
+                output.truncate(start + partial.written);
+                return Err(shift(partial.error));
+            }
+        }
+        self.buffer.copy_within(len .., 0);
+        self.buffer.truncate(self.buffer.len() - len);
+        self.position += len;
+        Ok(())
+    }
+}
+
+/// Encodes the data of a reader
+///
+/// Reading from an [`EncodeReader`] until the end gives the same result as first reading all the
+/// data from the underlying reader and then using [`Encoding::encode()`]. The data is read and
+/// encoded by chunks, so it never needs to be entirely in memory.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Read;
+/// // See the documentation of Encoder for why we need a static.
+/// static BASE64: data_encoding::Encoding = data_encoding::BASE64;
+/// let mut reader = BASE64.new_encode_reader(&b"hello world"[..]);
+/// let mut output = String::new();
+/// reader.read_to_string(&mut output).unwrap();
+/// assert_eq!(output, "aGVsbG8gd29ybGQ=");
+/// ```
+#[derive(Debug)]
+#[cfg(feature = "std")]
+pub struct EncodeReader<'a, R> {
+    encoding: &'a Encoding,
+    reader: R,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    position: usize,
+    eof: bool,
+}
+
+#[cfg(feature = "std")]
+impl<'a, R: std::io::Read> EncodeReader<'a, R> {
+    fn new(encoding: &'a Encoding, reader: R) -> Self {
+        EncodeReader {
+            encoding,
+            reader,
+            input: Vec::new(),
+            output: Vec::new(),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the underlying reader
+    ///
+    /// Data that has been read from the underlying reader but not yet returned is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Reads about `size` bytes of output from the underlying reader.
+    fn fill(&mut self, size: usize) -> std::io::Result<()> {
+        let align = self.encoding.encode_align();
+        let size = core::cmp::max(floor(size * self.encoding.bit() / 8, align), align);
+        let start = self.input.len();
+        self.input.resize(start + size, 0);
+        let len = match self.reader.read(&mut self.input[start ..]) {
+            Ok(len) => len,
+            Err(error) => {
+                self.input.truncate(start);
+                return Err(error);
+            }
+        };
+        self.input.truncate(start + len);
+        let len = if len == 0 {
+            self.eof = true;
+            self.input.len()
+        } else {
+            floor(self.input.len(), align)
+        };
+        self.output.resize(self.encoding.encode_len(len), 0);
+        self.encoding.encode_mut(&self.input[.. len], &mut self.output);
+        self.position = 0;
+        self.input.copy_within(len .., 0);
+        self.input.truncate(self.input.len() - len);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for EncodeReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.output.len() {
+            if self.eof || buf.is_empty() {
+                return Ok(0);
+            }
+            self.fill(buf.len())?;
+        }
+        let len = core::cmp::min(buf.len(), self.output.len() - self.position);
+        buf[.. len].copy_from_slice(&self.output[self.position ..][.. len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Decodes the data of a reader
+///
+/// Reading from a [`DecodeReader`] until the end gives the same result as first reading all the
+/// data from the underlying reader and then using [`Encoding::decode()`]. The data is read and
+/// decoded by chunks, so it never needs to be entirely in memory. Each call to [`read()`] reads at
+/// most as many bytes from the underlying reader as the length of its buffer.
+///
+/// Decoding errors are returned as [`std::io::Error`] of kind [`InvalidData`] wrapping a
+/// [`DecodeError`] whose position is relative to the whole data of the underlying reader. The
+/// data decoded from the chunk containing the error is discarded. The reader should not be used
+/// after an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Read;
+/// // See the documentation of Encoder for why we need a static.
+/// static BASE64: data_encoding::Encoding = data_encoding::BASE64;
+/// let mut reader = BASE64.new_decode_reader(&b"aGVsbG8gd29ybGQ="[..]);
+/// let mut output = Vec::new();
+/// reader.read_to_end(&mut output).unwrap();
+/// assert_eq!(output, b"hello world");
+///
+/// let mut reader = BASE64.new_decode_reader(&b"aGVsbG8gd29yb!Q="[..]);
+/// let error = reader.read_to_end(&mut output).unwrap_err();
+/// let error = error.get_ref().unwrap().downcast_ref::<data_encoding::DecodeError>().unwrap();
+/// assert_eq!(error.position, 13);
+/// ```
+///
+/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+/// [`read()`]: std::io::Read::read
+#[derive(Debug)]
+#[cfg(feature = "std")]
+pub struct DecodeReader<'a, R> {
+    encoding: &'a Encoding,
+    reader: R,
+    state: DecodeState,
+    output: Vec<u8>,
+    position: usize,
+    eof: bool,
+}
+
+#[cfg(feature = "std")]
+impl<'a, R: std::io::Read> DecodeReader<'a, R> {
+    fn new(encoding: &'a Encoding, reader: R) -> Self {
+        DecodeReader {
+            encoding,
+            reader,
+            state: DecodeState::new(),
+            output: Vec::new(),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the underlying reader
+    ///
+    /// Data that has been read from the underlying reader but not yet returned is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Reads at most `size` bytes from the underlying reader and decodes them.
+    fn fill(&mut self, size: usize) -> std::io::Result<()> {
+        let buffer = &mut self.state.buffer;
+        let start = buffer.len();
+        buffer.resize(start + size, 0);
+        let len = match self.reader.read(&mut buffer[start ..]) {
+            Ok(len) => len,
+            Err(error) => {
+                buffer.truncate(start);
+                return Err(error);
+            }
+        };
+        buffer.truncate(start + len);
+        let len = if len == 0 {
+            self.eof = true;
+            buffer.len()
+        } else {
+            self.state.complete_len(self.encoding)
+        };
+        self.output.clear();
+        self.position = 0;
+        self.state.decode(self.encoding, len, &mut self.output).map_err(|error| {
+            self.output.clear();
+            std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for DecodeReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.output.len() {
+            if self.eof || buf.is_empty() {
+                return Ok(0);
+            }
+            self.fill(buf.len())?;
+        }
+        let len = core::cmp::min(buf.len(), self.output.len() - self.position);
+        buf[.. len].copy_from_slice(&self.output[self.position ..][.. len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Encodes the data written to it into a writer
+///
+/// Writing data to an [`EncodeWriter`] and then calling [`EncodeWriter::finish()`] gives the same
+/// result as writing the encoding of the concatenated data. In particular, padding and wrapping
+/// are only introduced where [`Encoding::encode()`] would introduce them. Only complete blocks are
+/// encoded and written to the underlying writer, the rest is kept until the next write.
+///
+/// The last block is encoded (and possibly padded) when finishing the writer. This is also done
+/// when the writer is dropped, but errors are then ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Write;
+/// // See the documentation of Encoder for why we need a static.
+/// static BASE64: data_encoding::Encoding = data_encoding::BASE64;
+/// let mut writer = BASE64.new_encode_writer(Vec::new());
+/// writer.write_all(b"hello ").unwrap();
+/// writer.write_all(b"world").unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"aGVsbG8gd29ybGQ=");
+/// ```
+#[derive(Debug)]
+#[cfg(feature = "std")]
+pub struct EncodeWriter<'a, W: std::io::Write> {
+    encoding: &'a Encoding,
+    writer: Option<W>,
+    input: Vec<u8>,
+    output: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: std::io::Write> EncodeWriter<'a, W> {
+    fn new(encoding: &'a Encoding, writer: W) -> Self {
+        EncodeWriter { encoding, writer: Some(writer), input: Vec::new(), output: Vec::new() }
+    }
+
+    /// Encodes the last block and returns the underlying writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the underlying writer fails.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.flush_input()?;
+        Ok(self.writer.take().unwrap())
+    }
+
+    fn flush_input(&mut self) -> std::io::Result<()> {
+        self.output.resize(self.encoding.encode_len(self.input.len()), 0);
+        self.encoding.encode_mut(&self.input, &mut self.output);
+        self.input.clear();
+        self.writer.as_mut().unwrap().write_all(&self.output)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Drop for EncodeWriter<'_, W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            drop(self.flush_input());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for EncodeWriter<'_, W> {
+    /// Encodes all complete blocks and writes them to the underlying writer
+    ///
+    /// On success, the whole buffer is always consumed. On error, the buffer is not consumed but
+    /// some of its encoding may have been written to the underlying writer.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let start = self.input.len();
+        self.input.extend_from_slice(buf);
+        let len = floor(self.input.len(), self.encoding.encode_align());
+        self.output.resize(self.encoding.encode_len(len), 0);
+        self.encoding.encode_mut(&self.input[.. len], &mut self.output);
+        if let Err(error) = self.writer.as_mut().unwrap().write_all(&self.output) {
+            self.input.truncate(start);
+            return Err(error);
+        }
+        self.input.copy_within(len .., 0);
+        self.input.truncate(self.input.len() - len);
+        Ok(buf.len())
+    }
+
+    /// Flushes the underlying writer
+    ///
+    /// The last incomplete block is not encoded (to avoid introducing padding). Use
+    /// [`EncodeWriter::finish()`] for that.
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.as_mut().unwrap().flush()
+    }
+}
+
+/// Decodes the data written to it into a writer
+///
+/// Writing data to a [`DecodeWriter`] and then calling [`DecodeWriter::finish()`] gives the same
+/// result as writing the decoding of the concatenated data. Only complete blocks are decoded and
+/// written to the underlying writer, the rest is kept until the next write.
+///
+/// Decoding errors are returned as [`std::io::Error`] of kind [`InvalidData`] wrapping a
+/// [`DecodeError`] whose position is relative to the concatenation of all written data. The data
+/// decoded from the write containing the error is discarded. The writer should not be used after
+/// an error.
+///
+/// Contrary to [`EncodeWriter`], dropping the writer does not decode the last block. It must be
+/// finished with [`DecodeWriter::finish()`] to check that the last block is valid.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Write;
+/// // See the documentation of Encoder for why we need a static.
+/// static BASE64: data_encoding::Encoding = data_encoding::BASE64;
+/// let mut writer = BASE64.new_decode_writer(Vec::new());
+/// writer.write_all(b"aGVsbG8gd2").unwrap();
+/// writer.write_all(b"9ybGQ=").unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"hello world");
+///
+/// let mut writer = BASE64.new_decode_writer(Vec::new());
+/// writer.write_all(b"aGVsbG8gd2").unwrap();
+/// let error = writer.write_all(b"9yb!Q=").unwrap_err();
+/// let error = error.get_ref().unwrap().downcast_ref::<data_encoding::DecodeError>().unwrap();
+/// assert_eq!(error.position, 13);
+/// ```
+///
+/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+#[derive(Debug)]
+#[cfg(feature = "std")]
+pub struct DecodeWriter<'a, W> {
+    encoding: &'a Encoding,
+    writer: W,
+    state: DecodeState,
+    output: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: std::io::Write> DecodeWriter<'a, W> {
+    fn new(encoding: &'a Encoding, writer: W) -> Self {
+        DecodeWriter { encoding, writer, state: DecodeState::new(), output: Vec::new() }
+    }
+
+    /// Decodes the last block and returns the underlying writer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the last block is invalid or writing to the underlying writer fails.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        let len = self.state.buffer.len();
+        self.decode(len)?;
+        Ok(self.writer)
+    }
+
+    fn decode(&mut self, len: usize) -> std::io::Result<()> {
+        self.output.clear();
+        self.state
+            .decode(self.encoding, len, &mut self.output)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        self.writer.write_all(&self.output)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for DecodeWriter<'_, W> {
+    /// Decodes all complete blocks and writes them to the underlying writer
+    ///
+    /// On success, the whole buffer is always consumed.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.state.buffer.extend_from_slice(buf);
+        let len = self.state.complete_len(self.encoding);
+        self.decode(len)?;
+        Ok(buf.len())
+    }
+
+    /// Flushes the underlying writer
+    ///
+    /// The last incomplete block is not decoded. Use [`DecodeWriter::finish()`] for that.
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
 /// Wraps an encoding and input for display purposes.
 #[derive(Debug)]
 pub struct Display<'a> {
@@ -1844,12 +3439,13 @@ impl core::fmt::Display for Display<'_> {
 enum SpecificationErrorImpl {
     BadSize,
     NotAscii,
-    Duplicate(u8),
+    Duplicate(char),
     ExtraPadding,
     WrapLength,
     WrapWidth(u8),
     FromTo,
-    Undefined(u8),
+    Undefined(char),
+    TooManyCharacters,
 }
 #[cfg(feature = "alloc")]
 use crate::SpecificationErrorImpl::*;
@@ -1865,12 +3461,13 @@ impl core::fmt::Display for SpecificationError {
         match self.0 {
             BadSize => write!(f, "invalid number of symbols"),
             NotAscii => write!(f, "non-ascii character"),
-            Duplicate(c) => write!(f, "{:?} has conflicting definitions", c as char),
+            Duplicate(c) => write!(f, "{:?} has conflicting definitions", c),
             ExtraPadding => write!(f, "unnecessary padding"),
             WrapLength => write!(f, "invalid wrap width or separator length"),
             WrapWidth(x) => write!(f, "wrap width not a multiple of {}", x),
             FromTo => write!(f, "translate from/to length mismatch"),
-            Undefined(c) => write!(f, "{:?} is undefined", c as char),
+            Undefined(c) => write!(f, "{:?} is undefined", c),
+            TooManyCharacters => write!(f, "too many distinct characters"),
         }
     }
 }
@@ -1887,6 +3484,7 @@ impl std::error::Error for SpecificationError {
             WrapWidth(_) => "wrap width not a multiple",
             FromTo => "translate from/to length mismatch",
             Undefined(_) => "undefined character",
+            TooManyCharacters => "too many distinct characters",
         }
     }
 }
@@ -1913,6 +3511,11 @@ impl Specification {
     ///
     /// Returns an error if the specification is invalid.
     pub fn encoding(&self) -> Result<Encoding, SpecificationError> {
+        self.encoding_with_prefix(&[])
+    }
+
+    // Returns the specified encoding with a prefix at the beginning of each wrapped line.
+    fn encoding_with_prefix(&self, prefix: &[u8]) -> Result<Encoding, SpecificationError> {
         let symbols = self.symbols.as_bytes();
         let bit: u8 = match symbols.len() {
             2 => 1,
@@ -1929,7 +3532,7 @@ impl Specification {
             if v[i as usize] == x {
                 return Ok(());
             }
-            check!(SpecificationError(Duplicate(i)), v[i as usize] == INVALID);
+            check!(SpecificationError(Duplicate(i as char)), v[i as usize] == INVALID);
             v[i as usize] = x;
             Ok(())
         };
@@ -1956,16 +3559,19 @@ impl Specification {
         } else {
             let col = self.wrap.width;
             let end = self.wrap.separator.as_bytes();
-            check!(SpecificationError(WrapLength), col < 256 && end.len() < 256);
+            check!(
+                SpecificationError(WrapLength),
+                col < 256 && end.len() < 256 && prefix.len() < 256
+            );
             #[allow(clippy::cast_possible_truncation)] // no truncation
             let col = col as u8;
             #[allow(clippy::cast_possible_truncation)] // no truncation
             let dec = dec(bit as usize) as u8;
             check!(SpecificationError(WrapWidth(dec)), col % dec == 0);
-            for &i in end {
+            for &i in end.iter().chain(prefix) {
                 set(&mut values, i, IGNORE)?;
             }
-            Some((col, end))
+            Some((col, prefix, end))
         };
         let from = self.translate.from.as_bytes();
         let to = self.translate.to.as_bytes();
@@ -1973,7 +3579,7 @@ impl Specification {
         for i in 0 .. from.len() {
             check!(SpecificationError(NotAscii), to[i] < 128);
             let v = values[to[i] as usize];
-            check!(SpecificationError(Undefined(to[i])), v != INVALID);
+            check!(SpecificationError(Undefined(to[i] as char)), v != INVALID);
             set(&mut values, from[i], v)?;
         }
         let mut encoding = Vec::new();
@@ -1993,8 +3599,14 @@ impl Specification {
         if ctb {
             encoding[513] |= 0x10;
         }
-        if let Some((col, end)) = wrap {
+        if let Some((col, prefix, end)) = wrap {
             encoding.push(col);
+            if !prefix.is_empty() {
+                encoding[513] |= 0x20;
+                #[allow(clippy::cast_possible_truncation)] // no truncation
+                encoding.push(prefix.len() as u8);
+                encoding.extend_from_slice(prefix);
+            }
             encoding.extend_from_slice(end);
         } else if values.contains(&IGNORE) {
             encoding.push(0);
@@ -2808,3 +4420,61 @@ const BASE64URL_NOPAD_IMPL: &[u8] = &[
     128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128,
     128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 128, 30,
 ];
+
+// Predefined encodings by decreasing likelihood (used by `detect`).
+//
+// Smaller alphabets come first, because an input is less likely to fit them by chance. For a given
+// alphabet, strict encodings come before permissive ones, and padded ones before unpadded ones.
+#[cfg(feature = "alloc")]
+static PREDEFINED: &[(&str, &Encoding)] = &[
+    ("HEXLOWER", &HEXLOWER),
+    ("HEXUPPER", &HEXUPPER),
+    ("HEXLOWER_PERMISSIVE", &HEXLOWER_PERMISSIVE),
+    ("HEXUPPER_PERMISSIVE", &HEXUPPER_PERMISSIVE),
+    ("BASE32", &BASE32),
+    ("BASE32_NOPAD", &BASE32_NOPAD),
+    ("BASE32HEX", &BASE32HEX),
+    ("BASE32HEX_NOPAD", &BASE32HEX_NOPAD),
+    ("BASE32_DNSSEC", &BASE32_DNSSEC),
+    ("BASE32_DNSCURVE", &BASE32_DNSCURVE),
+    ("BASE32_NOPAD_NOCASE", &BASE32_NOPAD_NOCASE),
+    ("BASE32_NOPAD_VISUAL", &BASE32_NOPAD_VISUAL),
+    ("BASE64", &BASE64),
+    ("BASE64_NOPAD", &BASE64_NOPAD),
+    ("BASE64URL", &BASE64URL),
+    ("BASE64URL_NOPAD", &BASE64URL_NOPAD),
+    ("BASE64_MIME", &BASE64_MIME),
+    ("BASE64_MIME_PERMISSIVE", &BASE64_MIME_PERMISSIVE),
+];
+
+/// Returns the predefined encodings which canonically decode `input`
+///
+/// An encoding canonically decodes an input if decoding succeeds and encoding the result gives back
+/// the input. Each encoding is returned with the name of its constant. The encodings are ranked by
+/// decreasing likelihood: smaller alphabets come first (because an input is less likely to fit them
+/// by chance), then strict encodings before permissive ones, and padded encodings before unpadded
+/// ones.
+///
+/// Note that the empty input is canonically decoded by all encodings.
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::{detect, BASE64URL_NOPAD, HEXLOWER};
+/// let names = |input| detect(input).iter().map(|x| x.0).collect::<Vec<_>>();
+/// assert_eq!(names(b"SGVsbG8="), ["BASE64", "BASE64URL"]);
+/// assert_eq!(names(b"SGVsbG8"), ["BASE64_NOPAD", "BASE64URL_NOPAD"]);
+/// assert_eq!(names(b"48656c6c6f"), ["HEXLOWER", "HEXLOWER_PERMISSIVE"]);
+/// assert_eq!(detect(b"deadbeef")[0].1, &HEXLOWER);
+/// assert_eq!(detect(b"deadbeef").last().unwrap().1, &BASE64URL_NOPAD);
+/// assert!(detect(b"Hello world!").is_empty());
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn detect(input: &[u8]) -> Vec<(&'static str, &'static Encoding)> {
+    let canonical = |encoding: &Encoding| match encoding.decode(input) {
+        Ok(output) => encoding.encode(&output).as_bytes() == input,
+        Err(_) => false,
+    };
+    PREDEFINED.iter().copied().filter(|&(_, encoding)| canonical(encoding)).collect()
+}
@@ -0,0 +1,333 @@
+//! Serde support
+//!
+//! This module provides adapters to serialize byte fields as encoded strings (and deserialize them
+//! back) with `#[serde(with = "...")]`. There is one module per predefined encoding (e.g.
+//! [`base64`] for [`BASE64`] or [`hexlower`] for [`HEXLOWER`]) and a generic [`With`] adapter for
+//! any other encoding.
+//!
+//! The supported field types are the ones implementing [`Encode`] and [`Decode`]. This includes
+//! `Vec<u8>`, `[u8; N]`, and `Option` of those.
+//!
+//! This module needs the `serde` and `alloc` features (and Rust 1.56).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Message {
+//!     #[serde(with = "data_encoding::serde::hexlower")]
+//!     id: [u8; 4],
+//!     #[serde(with = "data_encoding::serde::base64")]
+//!     body: Vec<u8>,
+//!     #[serde(with = "data_encoding::serde::base64url_nopad")]
+//!     signature: Option<Vec<u8>>,
+//! }
+//!
+//! let message = Message { id: [0xde, 0xad, 0xbe, 0xef], body: b"Hi!".to_vec(), signature: None };
+//! let json = r#"{"id":"deadbeef","body":"SGkh","signature":null}"#;
+//! assert_eq!(serde_json::to_string(&message).unwrap(), json);
+//! assert_eq!(serde_json::from_str::<Message>(json).unwrap(), message);
+//! ```
+//!
+//! [`BASE64`]: ../constant.BASE64.html
+//! [`Decode`]: trait.Decode.html
+//! [`Encode`]: trait.Encode.html
+//! [`HEXLOWER`]: ../constant.HEXLOWER.html
+//! [`With`]: struct.With.html
+//! [`base64`]: base64/index.html
+//! [`hexlower`]: hexlower/index.html
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Error, Expected, Visitor};
+use serde::{Deserializer, Serialize, Serializer};
+
+use crate::Encoding;
+
+/// Types which can be serialized as an encoded string
+pub trait Encode {
+    /// Serializes `self` encoded with `encoding`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serializer fails.
+    fn serialize_with<S: Serializer>(
+        &self, encoding: &Encoding, serializer: S,
+    ) -> Result<S::Ok, S::Error>;
+}
+
+/// Types which can be deserialized from an encoded string
+pub trait Decode: Sized {
+    /// Deserializes a value encoded with `encoding`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deserializer fails or if the string is not valid for `encoding`.
+    fn deserialize_with<'de, D: Deserializer<'de>>(
+        encoding: &Encoding, deserializer: D,
+    ) -> Result<Self, D::Error>;
+}
+
+impl Encode for [u8] {
+    fn serialize_with<S: Serializer>(
+        &self, encoding: &Encoding, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encoding.encode(self))
+    }
+}
+
+impl Encode for Vec<u8> {
+    fn serialize_with<S: Serializer>(
+        &self, encoding: &Encoding, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self[..].serialize_with(encoding, serializer)
+    }
+}
+
+impl<const N: usize> Encode for [u8; N] {
+    fn serialize_with<S: Serializer>(
+        &self, encoding: &Encoding, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self[..].serialize_with(encoding, serializer)
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn serialize_with<S: Serializer>(
+        &self, encoding: &Encoding, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        (**self).serialize_with(encoding, serializer)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn serialize_with<S: Serializer>(
+        &self, encoding: &Encoding, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        struct Present<'a, T>(&'a Encoding, &'a T);
+        impl<T: Encode> Serialize for Present<'_, T> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.1.serialize_with(self.0, serializer)
+            }
+        }
+        match self {
+            None => serializer.serialize_none(),
+            Some(value) => serializer.serialize_some(&Present(encoding, value)),
+        }
+    }
+}
+
+struct BytesVisitor<'a>(&'a Encoding);
+
+impl<'de> Visitor<'de> for BytesVisitor<'_> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an encoded string")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Vec<u8>, E> {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        self.0.decode(v).map_err(E::custom)
+    }
+}
+
+impl Decode for Vec<u8> {
+    fn deserialize_with<'de, D: Deserializer<'de>>(
+        encoding: &Encoding, deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(BytesVisitor(encoding))
+    }
+}
+
+struct Length(usize);
+
+impl Expected for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an encoded string of {} bytes", self.0)
+    }
+}
+
+impl<const N: usize> Decode for [u8; N] {
+    fn deserialize_with<'de, D: Deserializer<'de>>(
+        encoding: &Encoding, deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let output = Vec::deserialize_with(encoding, deserializer)?;
+        let len = output.len();
+        <[u8; N]>::try_from(output).map_err(|_| D::Error::invalid_length(len, &Length(N)))
+    }
+}
+
+struct OptionVisitor<'a, T>(&'a Encoding, PhantomData<T>);
+
+impl<'de, T: Decode> Visitor<'de> for OptionVisitor<'_, T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an optional encoded string")
+    }
+
+    fn visit_none<E: Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<T>, D::Error> {
+        T::deserialize_with(self.0, deserializer).map(Some)
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn deserialize_with<'de, D: Deserializer<'de>>(
+        encoding: &Encoding, deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_option(OptionVisitor(encoding, PhantomData))
+    }
+}
+
+/// Provides the encoding of a [`With`] adapter
+///
+/// Note that a reference to a constant (like [`BASE64`]) is not `'static`. The encoding must be
+/// stored in a `static` first (possibly lazily initialized).
+///
+/// [`BASE64`]: ../constant.BASE64.html
+/// [`With`]: struct.With.html
+pub trait Adapter {
+    /// Returns the encoding to use
+    fn encoding() -> &'static Encoding;
+}
+
+/// Generic adapter for any encoding
+///
+/// The encoding is given by the [`Adapter`] type parameter. This adapter is used with
+/// `#[serde(with = "data_encoding::serde::With::<MyAdapter>")]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use data_encoding::serde::{Adapter, With};
+/// use data_encoding::{Encoding, BASE32_DNSSEC};
+/// use serde::{Deserialize, Serialize};
+///
+/// static DNSSEC: Encoding = BASE32_DNSSEC;
+///
+/// struct Dnssec;
+/// impl Adapter for Dnssec {
+///     fn encoding() -> &'static Encoding {
+///         &DNSSEC
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Record {
+///     #[serde(with = "With::<Dnssec>")]
+///     hash: [u8; 5],
+/// }
+///
+/// let record = Record { hash: *b"hello" };
+/// let json = r#"{"hash":"d1imor3f"}"#;
+/// assert_eq!(serde_json::to_string(&record).unwrap(), json);
+/// assert_eq!(serde_json::from_str::<Record>(json).unwrap(), record);
+/// ```
+///
+/// [`Adapter`]: trait.Adapter.html
+#[derive(Debug)]
+pub struct With<A: ?Sized>(PhantomData<A>);
+
+impl<A: Adapter + ?Sized> With<A> {
+    /// Serializes `value` encoded with the adapter encoding
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the serializer fails.
+    pub fn serialize<T: Encode + ?Sized, S: Serializer>(
+        value: &T, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize_with(A::encoding(), serializer)
+    }
+
+    /// Deserializes a value encoded with the adapter encoding
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the deserializer fails or if the string is not valid for the adapter
+    /// encoding.
+    pub fn deserialize<'de, T: Decode, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize_with(A::encoding(), deserializer)
+    }
+}
+
+macro_rules! adapters {
+    ($($module:ident: $constant:ident,)*) => {
+        $(
+            #[doc = concat!("Adapter for [`", stringify!($constant), "`]")]
+            ///
+            /// See the [module] documentation for more details.
+            ///
+            #[doc = concat!("[`", stringify!($constant), "`]: ../../constant.",
+                            stringify!($constant), ".html")]
+            /// [module]: ../index.html
+            pub mod $module {
+                use serde::{Deserializer, Serializer};
+
+                use super::{Decode, Encode};
+
+                /// Serializes `value` as an encoded string
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if the serializer fails.
+                pub fn serialize<T: Encode + ?Sized, S: Serializer>(
+                    value: &T, serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    value.serialize_with(&crate::$constant, serializer)
+                }
+
+                /// Deserializes a value from an encoded string
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if the deserializer fails or if the string is not valid.
+                pub fn deserialize<'de, T: Decode, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<T, D::Error> {
+                    T::deserialize_with(&crate::$constant, deserializer)
+                }
+            }
+        )*
+    };
+}
+
+adapters! {
+    hexlower: HEXLOWER,
+    hexlower_permissive: HEXLOWER_PERMISSIVE,
+    hexupper: HEXUPPER,
+    hexupper_permissive: HEXUPPER_PERMISSIVE,
+    base32: BASE32,
+    base32_nopad: BASE32_NOPAD,
+    base32_nopad_nocase: BASE32_NOPAD_NOCASE,
+    base32_nopad_visual: BASE32_NOPAD_VISUAL,
+    base32hex: BASE32HEX,
+    base32hex_nopad: BASE32HEX_NOPAD,
+    base32_dnssec: BASE32_DNSSEC,
+    base32_dnscurve: BASE32_DNSCURVE,
+    base64: BASE64,
+    base64_nopad: BASE64_NOPAD,
+    base64_mime: BASE64_MIME,
+    base64_mime_permissive: BASE64_MIME_PERMISSIVE,
+    base64url: BASE64URL,
+    base64url_nopad: BASE64URL_NOPAD,
+}
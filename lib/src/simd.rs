@@ -0,0 +1,693 @@
+// SIMD kernels for most significant bit first encodings of bit-width 4 (hexadecimal) and 6
+// (base64).
+//
+// The kernels are generic over the symbols and values tables. They only process the longest
+// prefix of the input that they support and return its length, which is always a multiple of the
+// block length. The caller is responsible for the rest of the input (using the scalar
+// implementation). When decoding (or checking), a kernel stops before the first chunk containing
+// a character which is not a symbol (including padding and ignored characters). This preserves
+// the error semantics of the scalar implementation, which will report the exact position.
+//
+// The x86_64 kernels are selected at runtime with the std feature and at compile-time otherwise.
+// The aarch64 kernels are selected at compile-time and need the neon feature (because the NEON
+// intrinsics are not available with the MSRV).
+
+// Intrinsics are safe to call in functions with the corresponding target features since Rust
+// 1.87, but not with the MSRV. We thus wrap them in unsafe blocks which may be unused.
+#![allow(unused_unsafe)]
+
+// Returns whether the kernels support the bit-width (assuming most significant bit first).
+pub(crate) fn supports(bit: usize) -> bool {
+    cfg!(any(
+        target_arch = "x86_64",
+        all(feature = "neon", target_arch = "aarch64", target_feature = "neon")
+    )) && (bit == 4 || bit == 6)
+}
+
+// Encodes the longest supported prefix of `input` and returns its length.
+//
+// The output length must be the encoded length of the input length.
+pub(crate) fn encode(bit: usize, symbols: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    let len = x86::encode(bit, symbols, input, output);
+    #[cfg(all(feature = "neon", target_arch = "aarch64", target_feature = "neon"))]
+    let len = neon::encode(bit, symbols, input, output);
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        all(feature = "neon", target_arch = "aarch64", target_feature = "neon")
+    )))]
+    let len = {
+        let _ = (symbols, input, output);
+        0
+    };
+    debug_assert_eq!(len * 8 % bit, 0);
+    len
+}
+
+// Decodes the longest supported prefix of `input` made of symbols and returns its length.
+//
+// The output length must be the decoded length of the input length.
+pub(crate) fn decode(bit: usize, values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    let len = x86::decode(bit, values, input, output);
+    #[cfg(all(feature = "neon", target_arch = "aarch64", target_feature = "neon"))]
+    let len = neon::decode(bit, values, input, output);
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        all(feature = "neon", target_arch = "aarch64", target_feature = "neon")
+    )))]
+    let len = {
+        let _ = (values, input, output);
+        0
+    };
+    debug_assert_eq!(len * bit % 8, 0);
+    len
+}
+
+// Returns the length of the longest supported prefix of `input` made of symbols.
+//
+// Contrary to the other kernels, this one supports all bit-widths (and bit-orders), because it
+// only checks that the values of the characters are not greater than 127.
+pub(crate) fn check(values: &[u8; 256], input: &[u8]) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    let len = x86::check(values, input);
+    #[cfg(all(feature = "neon", target_arch = "aarch64", target_feature = "neon"))]
+    let len = neon::check(values, input);
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        all(feature = "neon", target_arch = "aarch64", target_feature = "neon")
+    )))]
+    let len = {
+        let _ = (values, input);
+        0
+    };
+    len
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use core::arch::x86_64::*;
+
+    #[cfg(feature = "std")]
+    fn has_avx2() -> bool {
+        std::is_x86_feature_detected!("avx2")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn has_avx2() -> bool {
+        cfg!(target_feature = "avx2")
+    }
+
+    #[cfg(feature = "std")]
+    fn has_ssse3() -> bool {
+        std::is_x86_feature_detected!("ssse3")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn has_ssse3() -> bool {
+        cfg!(target_feature = "ssse3")
+    }
+
+    pub(super) fn encode(
+        bit: usize, symbols: &[u8; 256], input: &[u8], output: &mut [u8],
+    ) -> usize {
+        let mut len = 0;
+        if has_avx2() {
+            // SAFETY: The avx2 target feature is available.
+            len = unsafe {
+                match bit {
+                    4 => encode4_avx2(symbols, input, output),
+                    6 => encode6_avx2(symbols, input, output),
+                    _ => 0,
+                }
+            };
+        }
+        if has_ssse3() {
+            let input = &input[len ..];
+            let output = &mut output[len * 8 / bit ..];
+            // SAFETY: The ssse3 target feature is available.
+            len += unsafe {
+                match bit {
+                    4 => encode4_ssse3(symbols, input, output),
+                    6 => encode6_ssse3(symbols, input, output),
+                    _ => 0,
+                }
+            };
+        }
+        len
+    }
+
+    pub(super) fn decode(bit: usize, values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut len = 0;
+        if has_avx2() {
+            // SAFETY: The avx2 target feature is available.
+            len = unsafe {
+                match bit {
+                    4 => decode4_avx2(values, input, output),
+                    6 => decode6_avx2(values, input, output),
+                    _ => 0,
+                }
+            };
+        }
+        if has_ssse3() {
+            let input = &input[len ..];
+            let output = &mut output[len * bit / 8 ..];
+            // SAFETY: The ssse3 target feature is available.
+            len += unsafe {
+                match bit {
+                    4 => decode4_ssse3(values, input, output),
+                    6 => decode6_ssse3(values, input, output),
+                    _ => 0,
+                }
+            };
+        }
+        len
+    }
+
+    pub(super) fn check(values: &[u8; 256], input: &[u8]) -> usize {
+        let mut len = 0;
+        if has_avx2() {
+            // SAFETY: The avx2 target feature is available.
+            len = unsafe { check_avx2(values, input) };
+        }
+        if has_ssse3() {
+            // SAFETY: The ssse3 target feature is available.
+            len += unsafe { check_ssse3(values, &input[len ..]) };
+        }
+        len
+    }
+
+    // Loads the k-th 16-bytes table of `table`.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn table_128(table: &[u8; 256], k: usize) -> __m128i {
+        debug_assert!(k < 16);
+        // SAFETY: The 16 bytes are within `table` since k < 16.
+        unsafe { _mm_loadu_si128(table.as_ptr().add(16 * k) as *const __m128i) }
+    }
+
+    // Translates the bytes of `x` using the concatenation of `tables`.
+    //
+    // Bytes outside the tables translate to zero.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn lookup_128(tables: &[__m128i], x: __m128i) -> __m128i {
+        // SAFETY: The target feature of the function is available.
+        unsafe {
+            let mut r = _mm_setzero_si128();
+            for (k, &t) in tables.iter().enumerate() {
+                // The index has its most significant bit set (and thus selects zero) unless x - 16k
+                // is between 0 and 15.
+                let k = _mm_set1_epi8((16 * k) as i8);
+                let i = _mm_adds_epu8(_mm_sub_epi8(x, k), _mm_set1_epi8(0x70));
+                r = _mm_or_si128(r, _mm_shuffle_epi8(t, i));
+            }
+            r
+        }
+    }
+
+    // Translates the bytes of `x` using `values` (restricted to ASCII), setting the most
+    // significant bit of non-ASCII bytes.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn values_128(values: &[__m128i; 8], x: __m128i) -> __m128i {
+        // SAFETY: The target feature of the function is available.
+        unsafe { _mm_or_si128(lookup_128(values, x), _mm_and_si128(x, _mm_set1_epi8(-128))) }
+    }
+
+    // Splits 12 bytes (in groups of 3 bytes) into 16 6-bits values.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn unpack6_128(x: __m128i) -> __m128i {
+        // SAFETY: The target feature of the function is available.
+        unsafe {
+            let x = _mm_shuffle_epi8(
+                x,
+                _mm_set_epi8(10, 11, 9, 10, 7, 8, 6, 7, 4, 5, 3, 4, 1, 2, 0, 1),
+            );
+            let a = _mm_mulhi_epu16(
+                _mm_and_si128(x, _mm_set1_epi32(0x0fc0fc00)),
+                _mm_set1_epi32(0x04000040),
+            );
+            let b = _mm_mullo_epi16(
+                _mm_and_si128(x, _mm_set1_epi32(0x003f03f0)),
+                _mm_set1_epi32(0x01000010),
+            );
+            _mm_or_si128(a, b)
+        }
+    }
+
+    // Merges 16 6-bits values into 12 bytes (in groups of 3 bytes). The last 4 bytes are zero.
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn pack6_128(x: __m128i) -> __m128i {
+        // SAFETY: The target feature of the function is available.
+        unsafe {
+            let x = _mm_maddubs_epi16(x, _mm_set1_epi32(0x01400140));
+            let x = _mm_madd_epi16(x, _mm_set1_epi32(0x00011000));
+            _mm_shuffle_epi8(
+                x,
+                _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1),
+            )
+        }
+    }
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn encode4_ssse3(symbols: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 16 bytes from `input` at `i` and write 32 bytes to `output` at `2 * i`,
+        // which are both in bounds.
+        unsafe {
+            let s = table_128(symbols, 0);
+            let m = _mm_set1_epi8(0x0f);
+            while i + 16 <= input.len() {
+                let x = _mm_loadu_si128(input.as_ptr().add(i) as *const __m128i);
+                let hi = _mm_and_si128(_mm_srli_epi16(x, 4), m);
+                let lo = _mm_and_si128(x, m);
+                let o = output.as_mut_ptr().add(2 * i) as *mut __m128i;
+                _mm_storeu_si128(o, _mm_shuffle_epi8(s, _mm_unpacklo_epi8(hi, lo)));
+                _mm_storeu_si128(o.add(1), _mm_shuffle_epi8(s, _mm_unpackhi_epi8(hi, lo)));
+                i += 16;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn encode6_ssse3(symbols: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 16 bytes from `input` at `i` and write 16 bytes to `output` at `i / 3 *
+        // 4`, which are both in bounds (the latter because the output is longer than `(i + 16) /
+        // 3 * 4`).
+        unsafe {
+            let s = [
+                table_128(symbols, 0),
+                table_128(symbols, 1),
+                table_128(symbols, 2),
+                table_128(symbols, 3),
+            ];
+            while i + 16 <= input.len() {
+                let x = _mm_loadu_si128(input.as_ptr().add(i) as *const __m128i);
+                let y = lookup_128(&s, unpack6_128(x));
+                _mm_storeu_si128(output.as_mut_ptr().add(i / 3 * 4) as *mut __m128i, y);
+                i += 12;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn decode4_ssse3(values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 16 bytes from `input` at `i` and write 8 bytes to `output` at `i / 2`,
+        // which are both in bounds.
+        unsafe {
+            let v = values_tables(values);
+            while i + 16 <= input.len() {
+                let x = _mm_loadu_si128(input.as_ptr().add(i) as *const __m128i);
+                let y = values_128(&v, x);
+                if _mm_movemask_epi8(y) != 0 {
+                    break;
+                }
+                let y = _mm_maddubs_epi16(y, _mm_set1_epi16(0x0110));
+                let y = _mm_packus_epi16(y, y);
+                _mm_storel_epi64(output.as_mut_ptr().add(i / 2) as *mut __m128i, y);
+                i += 16;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn decode6_ssse3(values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 16 bytes from `input` at `i` and write 16 bytes to `output` at `i / 4 *
+        // 3`, which are both in bounds.
+        unsafe {
+            let v = values_tables(values);
+            while i + 16 <= input.len() && i / 4 * 3 + 16 <= output.len() {
+                let x = _mm_loadu_si128(input.as_ptr().add(i) as *const __m128i);
+                let y = values_128(&v, x);
+                if _mm_movemask_epi8(y) != 0 {
+                    break;
+                }
+                _mm_storeu_si128(output.as_mut_ptr().add(i / 4 * 3) as *mut __m128i, pack6_128(y));
+                i += 16;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn check_ssse3(values: &[u8; 256], input: &[u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 16 bytes from `input` at `i`, which is in bounds.
+        unsafe {
+            let v = values_tables(values);
+            while i + 16 <= input.len() {
+                let x = _mm_loadu_si128(input.as_ptr().add(i) as *const __m128i);
+                if _mm_movemask_epi8(values_128(&v, x)) != 0 {
+                    break;
+                }
+                i += 16;
+            }
+        }
+        i
+    }
+
+    #[inline]
+    #[target_feature(enable = "ssse3")]
+    unsafe fn values_tables(values: &[u8; 256]) -> [__m128i; 8] {
+        // SAFETY: The target feature of the function is available.
+        unsafe {
+            [
+                table_128(values, 0),
+                table_128(values, 1),
+                table_128(values, 2),
+                table_128(values, 3),
+                table_128(values, 4),
+                table_128(values, 5),
+                table_128(values, 6),
+                table_128(values, 7),
+            ]
+        }
+    }
+
+    // Same as lookup_128 but for each 128-bits lane.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn lookup_256(tables: &[__m256i], x: __m256i) -> __m256i {
+        // SAFETY: The target feature of the function is available.
+        unsafe {
+            let mut r = _mm256_setzero_si256();
+            for (k, &t) in tables.iter().enumerate() {
+                let k = _mm256_set1_epi8((16 * k) as i8);
+                let i = _mm256_adds_epu8(_mm256_sub_epi8(x, k), _mm256_set1_epi8(0x70));
+                r = _mm256_or_si256(r, _mm256_shuffle_epi8(t, i));
+            }
+            r
+        }
+    }
+
+    // Same as values_128 but for each 128-bits lane.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn values_256(values: &[__m256i; 8], x: __m256i) -> __m256i {
+        // SAFETY: The target feature of the function is available.
+        unsafe {
+            _mm256_or_si256(lookup_256(values, x), _mm256_and_si256(x, _mm256_set1_epi8(-128)))
+        }
+    }
+
+    // Broadcasts the tables of `values_tables` to both lanes.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn values_tables_256(values: &[u8; 256]) -> [__m256i; 8] {
+        // SAFETY: The target feature of the function is available.
+        unsafe {
+            let mut r = [_mm256_setzero_si256(); 8];
+            for (r, &v) in r.iter_mut().zip(values_tables(values).iter()) {
+                *r = _mm256_broadcastsi128_si256(v);
+            }
+            r
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn encode4_avx2(symbols: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 32 bytes from `input` at `i` and write 64 bytes to `output` at `2 * i`,
+        // which are both in bounds.
+        unsafe {
+            let s = _mm256_broadcastsi128_si256(table_128(symbols, 0));
+            let m = _mm256_set1_epi8(0x0f);
+            while i + 32 <= input.len() {
+                let x = _mm256_loadu_si256(input.as_ptr().add(i) as *const __m256i);
+                let hi = _mm256_and_si256(_mm256_srli_epi16(x, 4), m);
+                let lo = _mm256_and_si256(x, m);
+                let a = _mm256_unpacklo_epi8(hi, lo);
+                let b = _mm256_unpackhi_epi8(hi, lo);
+                let o = output.as_mut_ptr().add(2 * i) as *mut __m256i;
+                let y = _mm256_permute2x128_si256(a, b, 0x20);
+                _mm256_storeu_si256(o, _mm256_shuffle_epi8(s, y));
+                let y = _mm256_permute2x128_si256(a, b, 0x31);
+                _mm256_storeu_si256(o.add(1), _mm256_shuffle_epi8(s, y));
+                i += 32;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn encode6_avx2(symbols: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 28 bytes from `input` at `i` and write 32 bytes to `output` at `i / 3 *
+        // 4`, which are both in bounds (the latter because the output is longer than `(i + 28) /
+        // 3 * 4`).
+        unsafe {
+            let mut s = [_mm256_setzero_si256(); 4];
+            for (k, s) in s.iter_mut().enumerate() {
+                *s = _mm256_broadcastsi128_si256(table_128(symbols, k));
+            }
+            while i + 28 <= input.len() {
+                let p = input.as_ptr().add(i);
+                let lo = _mm_loadu_si128(p as *const __m128i);
+                let hi = _mm_loadu_si128(p.add(12) as *const __m128i);
+                let x = _mm256_inserti128_si256(_mm256_castsi128_si256(lo), hi, 1);
+                let x = _mm256_shuffle_epi8(
+                    x,
+                    _mm256_set_epi8(
+                        10, 11, 9, 10, 7, 8, 6, 7, 4, 5, 3, 4, 1, 2, 0, 1, 10, 11, 9, 10, 7, 8, 6,
+                        7, 4, 5, 3, 4, 1, 2, 0, 1,
+                    ),
+                );
+                let a = _mm256_and_si256(x, _mm256_set1_epi32(0x0fc0fc00));
+                let a = _mm256_mulhi_epu16(a, _mm256_set1_epi32(0x04000040));
+                let b = _mm256_and_si256(x, _mm256_set1_epi32(0x003f03f0));
+                let b = _mm256_mullo_epi16(b, _mm256_set1_epi32(0x01000010));
+                let y = lookup_256(&s, _mm256_or_si256(a, b));
+                _mm256_storeu_si256(output.as_mut_ptr().add(i / 3 * 4) as *mut __m256i, y);
+                i += 24;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn check_avx2(values: &[u8; 256], input: &[u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 32 bytes from `input` at `i`, which is in bounds.
+        unsafe {
+            let v = values_tables_256(values);
+            while i + 32 <= input.len() {
+                let x = _mm256_loadu_si256(input.as_ptr().add(i) as *const __m256i);
+                if _mm256_movemask_epi8(values_256(&v, x)) != 0 {
+                    break;
+                }
+                i += 32;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn decode4_avx2(values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 32 bytes from `input` at `i` and write 16 bytes to `output` at `i / 2`,
+        // which are both in bounds.
+        unsafe {
+            let v = values_tables_256(values);
+            while i + 32 <= input.len() {
+                let x = _mm256_loadu_si256(input.as_ptr().add(i) as *const __m256i);
+                let y = values_256(&v, x);
+                if _mm256_movemask_epi8(y) != 0 {
+                    break;
+                }
+                let y = _mm256_maddubs_epi16(y, _mm256_set1_epi16(0x0110));
+                let y = _mm256_permute4x64_epi64(_mm256_packus_epi16(y, y), 0x08);
+                let o = output.as_mut_ptr().add(i / 2) as *mut __m128i;
+                _mm_storeu_si128(o, _mm256_castsi256_si128(y));
+                i += 32;
+            }
+        }
+        i
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn decode6_avx2(values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: We read 32 bytes from `input` at `i` and write 32 bytes to `output` at `i / 4 *
+        // 3`, which are both in bounds.
+        unsafe {
+            let v = values_tables_256(values);
+            while i + 32 <= input.len() && i / 4 * 3 + 32 <= output.len() {
+                let x = _mm256_loadu_si256(input.as_ptr().add(i) as *const __m256i);
+                let y = values_256(&v, x);
+                if _mm256_movemask_epi8(y) != 0 {
+                    break;
+                }
+                let y = _mm256_maddubs_epi16(y, _mm256_set1_epi32(0x01400140));
+                let y = _mm256_madd_epi16(y, _mm256_set1_epi32(0x00011000));
+                let y = _mm256_shuffle_epi8(
+                    y,
+                    _mm256_setr_epi8(
+                        2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1, 2, 1, 0, 6, 5, 4,
+                        10, 9, 8, 14, 13, 12, -1, -1, -1, -1,
+                    ),
+                );
+                let y = _mm256_permutevar8x32_epi32(y, _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 7, 7));
+                _mm256_storeu_si256(output.as_mut_ptr().add(i / 4 * 3) as *mut __m256i, y);
+                i += 32;
+            }
+        }
+        i
+    }
+}
+
+#[cfg(all(feature = "neon", target_arch = "aarch64", target_feature = "neon"))]
+#[allow(clippy::incompatible_msrv)]
+mod neon {
+    use core::arch::aarch64::*;
+
+    // Translates the bytes of `x` using `values` (restricted to ASCII), setting the most
+    // significant bit of non-ASCII bytes.
+    #[inline]
+    fn lookup(v: &(uint8x16x4_t, uint8x16x4_t), x: uint8x16_t) -> uint8x16_t {
+        // SAFETY: The neon target feature is enabled.
+        unsafe {
+            let a = vqtbl4q_u8(v.0, x);
+            let b = vqtbl4q_u8(v.1, veorq_u8(x, vdupq_n_u8(0x40)));
+            vorrq_u8(vorrq_u8(a, b), vandq_u8(x, vdupq_n_u8(0x80)))
+        }
+    }
+
+    fn values_tables(values: &[u8; 256]) -> (uint8x16x4_t, uint8x16x4_t) {
+        // SAFETY: The neon target feature is enabled and we read the first 128 bytes of `values`.
+        unsafe { (vld1q_u8_x4(values.as_ptr()), vld1q_u8_x4(values.as_ptr().add(64))) }
+    }
+
+    pub(super) fn encode(
+        bit: usize, symbols: &[u8; 256], input: &[u8], output: &mut [u8],
+    ) -> usize {
+        match bit {
+            4 => encode4(symbols, input, output),
+            6 => encode6(symbols, input, output),
+            _ => 0,
+        }
+    }
+
+    pub(super) fn decode(bit: usize, values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        match bit {
+            4 => decode4(values, input, output),
+            6 => decode6(values, input, output),
+            _ => 0,
+        }
+    }
+
+    pub(super) fn check(values: &[u8; 256], input: &[u8]) -> usize {
+        let mut i = 0;
+        let v = values_tables(values);
+        // SAFETY: The neon target feature is enabled. We read 16 bytes from `input` at `i`, which
+        // is in bounds.
+        unsafe {
+            while i + 16 <= input.len() {
+                if vmaxvq_u8(lookup(&v, vld1q_u8(input.as_ptr().add(i)))) & 0x80 != 0 {
+                    break;
+                }
+                i += 16;
+            }
+        }
+        i
+    }
+
+    fn encode4(symbols: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: The neon target feature is enabled. We read 16 bytes from `input` at `i` and
+        // write 32 bytes to `output` at `2 * i`, which are both in bounds.
+        unsafe {
+            let s = vld1q_u8(symbols.as_ptr());
+            let m = vdupq_n_u8(0x0f);
+            while i + 16 <= input.len() {
+                let x = vld1q_u8(input.as_ptr().add(i));
+                let hi = vqtbl1q_u8(s, vshrq_n_u8(x, 4));
+                let lo = vqtbl1q_u8(s, vandq_u8(x, m));
+                vst2q_u8(output.as_mut_ptr().add(2 * i), uint8x16x2_t(hi, lo));
+                i += 16;
+            }
+        }
+        i
+    }
+
+    fn encode6(symbols: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: The neon target feature is enabled. We read 48 bytes from `input` at `i` and
+        // write 64 bytes to `output` at `i / 3 * 4`, which are both in bounds.
+        unsafe {
+            let s = vld1q_u8_x4(symbols.as_ptr());
+            let m = vdupq_n_u8(0x3f);
+            while i + 48 <= input.len() {
+                let x = vld3q_u8(input.as_ptr().add(i));
+                let a = vshrq_n_u8(x.0, 2);
+                let b = vandq_u8(vorrq_u8(vshlq_n_u8(x.0, 4), vshrq_n_u8(x.1, 4)), m);
+                let c = vandq_u8(vorrq_u8(vshlq_n_u8(x.1, 2), vshrq_n_u8(x.2, 6)), m);
+                let d = vandq_u8(x.2, m);
+                let y = uint8x16x4_t(
+                    vqtbl4q_u8(s, a),
+                    vqtbl4q_u8(s, b),
+                    vqtbl4q_u8(s, c),
+                    vqtbl4q_u8(s, d),
+                );
+                vst4q_u8(output.as_mut_ptr().add(i / 3 * 4), y);
+                i += 48;
+            }
+        }
+        i
+    }
+
+    fn decode4(values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        let v = values_tables(values);
+        // SAFETY: The neon target feature is enabled. We read 32 bytes from `input` at `i` and
+        // write 16 bytes to `output` at `i / 2`, which are both in bounds.
+        unsafe {
+            while i + 32 <= input.len() {
+                let x = vld2q_u8(input.as_ptr().add(i));
+                let hi = lookup(&v, x.0);
+                let lo = lookup(&v, x.1);
+                if vmaxvq_u8(vorrq_u8(hi, lo)) & 0x80 != 0 {
+                    break;
+                }
+                vst1q_u8(output.as_mut_ptr().add(i / 2), vorrq_u8(vshlq_n_u8(hi, 4), lo));
+                i += 32;
+            }
+        }
+        i
+    }
+
+    fn decode6(values: &[u8; 256], input: &[u8], output: &mut [u8]) -> usize {
+        let mut i = 0;
+        let v = values_tables(values);
+        // SAFETY: The neon target feature is enabled. We read 64 bytes from `input` at `i` and
+        // write 48 bytes to `output` at `i / 4 * 3`, which are both in bounds.
+        unsafe {
+            while i + 64 <= input.len() {
+                let x = vld4q_u8(input.as_ptr().add(i));
+                let a = lookup(&v, x.0);
+                let b = lookup(&v, x.1);
+                let c = lookup(&v, x.2);
+                let d = lookup(&v, x.3);
+                if vmaxvq_u8(vorrq_u8(vorrq_u8(a, b), vorrq_u8(c, d))) & 0x80 != 0 {
+                    break;
+                }
+                let y = uint8x16x3_t(
+                    vorrq_u8(vshlq_n_u8(a, 2), vshrq_n_u8(b, 4)),
+                    vorrq_u8(vshlq_n_u8(b, 4), vshrq_n_u8(c, 2)),
+                    vorrq_u8(vshlq_n_u8(c, 6), d),
+                );
+                vst3q_u8(output.as_mut_ptr().add(i / 4 * 3), y);
+                i += 64;
+            }
+        }
+        i
+    }
+}
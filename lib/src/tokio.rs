@@ -0,0 +1,441 @@
+//! Tokio support
+//!
+//! This module provides asynchronous counterparts of [`EncodeReader`], [`DecodeReader`],
+//! [`EncodeWriter`], and [`DecodeWriter`] for the [`AsyncRead`] and [`AsyncWrite`] traits of
+//! [tokio]. They are created with [`Encoding::new_async_encode_reader()`],
+//! [`Encoding::new_async_decode_reader()`], [`Encoding::new_async_encode_writer()`], and
+//! [`Encoding::new_async_decode_writer()`].
+//!
+//! Incomplete blocks are kept between calls to [`poll_read()`] and [`poll_write()`], such that
+//! the result is the same as encoding or decoding the concatenated data. The underlying readers
+//! and writers must be [`Unpin`] (use [`Box::pin()`] otherwise).
+//!
+//! This module needs the `tokio` and `std` features.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tokio::io::{AsyncReadExt, AsyncWriteExt};
+//! // See the documentation of Encoder for why we need a static.
+//! static BASE64: data_encoding::Encoding = data_encoding::BASE64;
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let mut writer = BASE64.new_async_encode_writer(Vec::new());
+//! writer.write_all(b"hello ").await.unwrap();
+//! writer.write_all(b"world").await.unwrap();
+//! writer.shutdown().await.unwrap();
+//! let encoded = writer.into_inner();
+//! assert_eq!(encoded, b"aGVsbG8gd29ybGQ=");
+//!
+//! let mut reader = BASE64.new_async_decode_reader(&encoded[..]);
+//! let mut output = Vec::new();
+//! reader.read_to_end(&mut output).await.unwrap();
+//! assert_eq!(output, b"hello world");
+//! # });
+//! ```
+//!
+//! [`AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+//! [`AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+//! [`Box::pin()`]: std::boxed::Box::pin
+//! [`DecodeReader`]: ../struct.DecodeReader.html
+//! [`DecodeWriter`]: ../struct.DecodeWriter.html
+//! [`EncodeReader`]: ../struct.EncodeReader.html
+//! [`EncodeWriter`]: ../struct.EncodeWriter.html
+//! [`Encoding::new_async_decode_reader()`]: ../struct.Encoding.html#method.new_async_decode_reader
+//! [`Encoding::new_async_decode_writer()`]: ../struct.Encoding.html#method.new_async_decode_writer
+//! [`Encoding::new_async_encode_reader()`]: ../struct.Encoding.html#method.new_async_encode_reader
+//! [`Encoding::new_async_encode_writer()`]: ../struct.Encoding.html#method.new_async_encode_writer
+//! [`poll_read()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html#tymethod.poll_read
+//! [`poll_write()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#tymethod.poll_write
+//! [tokio]: https://docs.rs/tokio
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::vec::Vec;
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{floor, DecodeState, Encoding};
+
+macro_rules! ready {
+    ($e: expr) => {
+        match $e {
+            Poll::Ready(x) => x,
+            Poll::Pending => return Poll::Pending,
+        }
+    };
+}
+
+// Reads from `reader` to the end of `buffer` (extended by `size` bytes) and returns the number of
+// bytes read. On error or if pending, the buffer is left unchanged.
+fn poll_fill<R: AsyncRead + Unpin>(
+    reader: &mut R, cx: &mut Context<'_>, buffer: &mut Vec<u8>, size: usize,
+) -> Poll<io::Result<usize>> {
+    let start = buffer.len();
+    buffer.resize(start + size, 0);
+    let mut buf = ReadBuf::new(&mut buffer[start ..]);
+    let result = Pin::new(reader).poll_read(cx, &mut buf);
+    let len = buf.filled().len();
+    match result {
+        Poll::Ready(Ok(())) => buffer.truncate(start + len),
+        _ => buffer.truncate(start),
+    }
+    result.map_ok(|()| len)
+}
+
+// Writes `output[*position ..]` to `writer` and advances `position` accordingly.
+fn poll_drain<W: AsyncWrite + Unpin>(
+    writer: &mut W, cx: &mut Context<'_>, output: &[u8], position: &mut usize,
+) -> Poll<io::Result<()>> {
+    while *position < output.len() {
+        let len = ready!(Pin::new(&mut *writer).poll_write(cx, &output[*position ..]))?;
+        if len == 0 {
+            return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+        }
+        *position += len;
+    }
+    Poll::Ready(Ok(()))
+}
+
+// Copies as much pending output as possible to `buf`.
+fn copy(output: &[u8], position: &mut usize, buf: &mut ReadBuf<'_>) {
+    let len = core::cmp::min(buf.remaining(), output.len() - *position);
+    buf.put_slice(&output[*position ..][.. len]);
+    *position += len;
+}
+
+/// Encodes the data of an asynchronous reader
+///
+/// This is the asynchronous version of [`EncodeReader`]. Reading until the end gives the same
+/// result as first reading all the data from the underlying reader and then using
+/// [`Encoding::encode()`].
+///
+/// [`EncodeReader`]: ../struct.EncodeReader.html
+/// [`Encoding::encode()`]: ../struct.Encoding.html#method.encode
+#[derive(Debug)]
+pub struct AsyncEncodeReader<'a, R> {
+    encoding: &'a Encoding,
+    reader: R,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    position: usize,
+    eof: bool,
+}
+
+impl<'a, R: AsyncRead + Unpin> AsyncEncodeReader<'a, R> {
+    pub(crate) fn new(encoding: &'a Encoding, reader: R) -> Self {
+        AsyncEncodeReader {
+            encoding,
+            reader,
+            input: Vec::new(),
+            output: Vec::new(),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the underlying reader
+    ///
+    /// Data that has been read from the underlying reader but not yet returned is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Reads about `size` bytes of output from the underlying reader.
+    fn poll_fill(&mut self, cx: &mut Context<'_>, size: usize) -> Poll<io::Result<()>> {
+        let align = self.encoding.encode_align();
+        let size = core::cmp::max(floor(size * self.encoding.bit() / 8, align), align);
+        let len = ready!(poll_fill(&mut self.reader, cx, &mut self.input, size))?;
+        let len = if len == 0 {
+            self.eof = true;
+            self.input.len()
+        } else {
+            floor(self.input.len(), align)
+        };
+        self.output.resize(self.encoding.encode_len(len), 0);
+        self.encoding.encode_mut(&self.input[.. len], &mut self.output);
+        self.position = 0;
+        self.input.copy_within(len .., 0);
+        self.input.truncate(self.input.len() - len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncEncodeReader<'_, R> {
+    fn poll_read(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        while this.position == this.output.len() {
+            if this.eof || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            ready!(this.poll_fill(cx, buf.remaining()))?;
+        }
+        copy(&this.output, &mut this.position, buf);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Decodes the data of an asynchronous reader
+///
+/// This is the asynchronous version of [`DecodeReader`]. Reading until the end gives the same
+/// result as first reading all the data from the underlying reader and then using
+/// [`Encoding::decode()`].
+///
+/// Decoding errors are returned as [`std::io::Error`] of kind [`InvalidData`] wrapping a
+/// [`DecodeError`] whose position is relative to the whole data of the underlying reader. The
+/// reader should not be used after an error.
+///
+/// [`DecodeError`]: ../struct.DecodeError.html
+/// [`DecodeReader`]: ../struct.DecodeReader.html
+/// [`Encoding::decode()`]: ../struct.Encoding.html#method.decode
+/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+#[derive(Debug)]
+pub struct AsyncDecodeReader<'a, R> {
+    encoding: &'a Encoding,
+    reader: R,
+    state: DecodeState,
+    output: Vec<u8>,
+    position: usize,
+    eof: bool,
+}
+
+impl<'a, R: AsyncRead + Unpin> AsyncDecodeReader<'a, R> {
+    pub(crate) fn new(encoding: &'a Encoding, reader: R) -> Self {
+        AsyncDecodeReader {
+            encoding,
+            reader,
+            state: DecodeState::new(),
+            output: Vec::new(),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the underlying reader
+    ///
+    /// Data that has been read from the underlying reader but not yet returned is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Reads at most `size` bytes from the underlying reader and decodes them.
+    fn poll_fill(&mut self, cx: &mut Context<'_>, size: usize) -> Poll<io::Result<()>> {
+        let len = ready!(poll_fill(&mut self.reader, cx, &mut self.state.buffer, size))?;
+        let len = if len == 0 {
+            self.eof = true;
+            self.state.buffer.len()
+        } else {
+            self.state.complete_len(self.encoding)
+        };
+        self.output.clear();
+        self.position = 0;
+        let result = self.state.decode(self.encoding, len, &mut self.output);
+        Poll::Ready(result.map_err(|error| {
+            self.output.clear();
+            io::Error::new(io::ErrorKind::InvalidData, error)
+        }))
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncDecodeReader<'_, R> {
+    fn poll_read(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        while this.position == this.output.len() {
+            if this.eof || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            ready!(this.poll_fill(cx, buf.remaining()))?;
+        }
+        copy(&this.output, &mut this.position, buf);
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Encodes the data written to it into an asynchronous writer
+///
+/// This is the asynchronous version of [`EncodeWriter`]. Writing data and then shutting down the
+/// writer gives the same result as writing the encoding of the concatenated data. Only complete
+/// blocks are encoded, the rest is kept until the next write.
+///
+/// The encoded data is written to the underlying writer on the next call to [`poll_write()`],
+/// [`poll_flush()`], or [`poll_shutdown()`]. The last block is encoded (and possibly padded) when
+/// shutting down the writer. Contrary to [`EncodeWriter`], this is not done when the writer is
+/// dropped.
+///
+/// [`EncodeWriter`]: ../struct.EncodeWriter.html
+/// [`poll_flush()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#tymethod.poll_flush
+/// [`poll_shutdown()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#tymethod.poll_shutdown
+/// [`poll_write()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#tymethod.poll_write
+#[derive(Debug)]
+pub struct AsyncEncodeWriter<'a, W> {
+    encoding: &'a Encoding,
+    writer: W,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    position: usize,
+}
+
+impl<'a, W: AsyncWrite + Unpin> AsyncEncodeWriter<'a, W> {
+    pub(crate) fn new(encoding: &'a Encoding, writer: W) -> Self {
+        AsyncEncodeWriter { encoding, writer, input: Vec::new(), output: Vec::new(), position: 0 }
+    }
+
+    /// Returns the underlying writer
+    ///
+    /// Data that has been written but not yet encoded or written to the underlying writer is lost.
+    /// Shut down the writer first to avoid that.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    // Encodes the first `len` bytes of input.
+    fn encode(&mut self, len: usize) {
+        self.output.resize(self.encoding.encode_len(len), 0);
+        self.encoding.encode_mut(&self.input[.. len], &mut self.output);
+        self.position = 0;
+        self.input.copy_within(len .., 0);
+        self.input.truncate(self.input.len() - len);
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncEncodeWriter<'_, W> {
+    /// Encodes all complete blocks
+    ///
+    /// The previously encoded data is first written to the underlying writer. Then the whole
+    /// buffer is always consumed.
+    fn poll_write(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        this.input.extend_from_slice(buf);
+        this.encode(floor(this.input.len(), this.encoding.encode_align()));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    /// Writes the encoded data and flushes the underlying writer
+    ///
+    /// The last incomplete block is not encoded (to avoid introducing padding). Shut down the
+    /// writer for that.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        Pin::new(&mut this.writer).poll_flush(cx)
+    }
+
+    /// Encodes the last block, writes the encoded data, and shuts down the underlying writer
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        if !this.input.is_empty() {
+            this.encode(this.input.len());
+            ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        }
+        Pin::new(&mut this.writer).poll_shutdown(cx)
+    }
+}
+
+/// Decodes the data written to it into an asynchronous writer
+///
+/// This is the asynchronous version of [`DecodeWriter`]. Writing data and then shutting down the
+/// writer gives the same result as writing the decoding of the concatenated data. Only complete
+/// blocks are decoded, the rest is kept until the next write.
+///
+/// The decoded data is written to the underlying writer on the next call to [`poll_write()`],
+/// [`poll_flush()`], or [`poll_shutdown()`]. The last block is decoded when shutting down the
+/// writer.
+///
+/// Decoding errors are returned as [`std::io::Error`] of kind [`InvalidData`] wrapping a
+/// [`DecodeError`] whose position is relative to the concatenation of all written data. The data
+/// decoded from the write containing the error is discarded. The writer should not be used after
+/// an error.
+///
+/// [`DecodeError`]: ../struct.DecodeError.html
+/// [`DecodeWriter`]: ../struct.DecodeWriter.html
+/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+/// [`poll_flush()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#tymethod.poll_flush
+/// [`poll_shutdown()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#tymethod.poll_shutdown
+/// [`poll_write()`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html#tymethod.poll_write
+#[derive(Debug)]
+pub struct AsyncDecodeWriter<'a, W> {
+    encoding: &'a Encoding,
+    writer: W,
+    state: DecodeState,
+    output: Vec<u8>,
+    position: usize,
+}
+
+impl<'a, W: AsyncWrite + Unpin> AsyncDecodeWriter<'a, W> {
+    pub(crate) fn new(encoding: &'a Encoding, writer: W) -> Self {
+        AsyncDecodeWriter {
+            encoding,
+            writer,
+            state: DecodeState::new(),
+            output: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns the underlying writer
+    ///
+    /// Data that has been written but not yet decoded or written to the underlying writer is lost.
+    /// Shut down the writer first to avoid that.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    // Decodes the first `len` bytes of the buffer.
+    fn decode(&mut self, len: usize) -> io::Result<()> {
+        self.output.clear();
+        self.position = 0;
+        let result = self.state.decode(self.encoding, len, &mut self.output);
+        result.map_err(|error| {
+            self.output.clear();
+            io::Error::new(io::ErrorKind::InvalidData, error)
+        })
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncDecodeWriter<'_, W> {
+    /// Decodes all complete blocks
+    ///
+    /// The previously decoded data is first written to the underlying writer. Then, on success,
+    /// the whole buffer is always consumed.
+    fn poll_write(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        this.state.buffer.extend_from_slice(buf);
+        let len = this.state.complete_len(this.encoding);
+        this.decode(len)?;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    /// Writes the decoded data and flushes the underlying writer
+    ///
+    /// The last incomplete block is not decoded. Shut down the writer for that.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        Pin::new(&mut this.writer).poll_flush(cx)
+    }
+
+    /// Decodes the last block, writes the decoded data, and shuts down the underlying writer
+    ///
+    /// Returns an error if the last block is invalid.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        if !this.state.buffer.is_empty() {
+            let len = this.state.buffer.len();
+            this.decode(len)?;
+            ready!(poll_drain(&mut this.writer, cx, &this.output, &mut this.position))?;
+        }
+        Pin::new(&mut this.writer).poll_shutdown(cx)
+    }
+}
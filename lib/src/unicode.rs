@@ -0,0 +1,265 @@
+//! Encodings with Unicode symbols
+//!
+//! A [`UnicodeEncoding`] is like an [`Encoding`] except that its symbols, padding, ignored
+//! characters, wrapping separator, and translated characters may be arbitrary [`char`]s instead of
+//! ASCII characters. This permits alphabets like Braille patterns or emojis. It is built from a
+//! [`Specification`] with [`Specification::unicode_encoding()`] and follows the same semantics
+//! (bit-width, bit-order, trailing bits, padding, ignore, wrap, and translate).
+//!
+//! Encoding produces a [`String`] and decoding takes a [`str`]. Decoding error positions are byte
+//! offsets in the input.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use data_encoding::Specification;
+//! let mut spec = Specification::new();
+//! spec.symbols.push_str("🌑🌒🌓🌔🌕🌖🌗🌘");
+//! spec.padding = Some('🌚');
+//! let moon = spec.unicode_encoding().unwrap();
+//! assert_eq!(moon.encode(b"Hi"), "🌓🌓🌑🌗🌕🌕🌚🌚");
+//! assert_eq!(moon.decode("🌓🌓🌑🌗🌕🌕🌚🌚").unwrap(), b"Hi");
+//! ```
+//!
+//! [`Encoding`]: ../struct.Encoding.html
+//! [`Specification`]: ../struct.Specification.html
+//! [`Specification::unicode_encoding()`]: ../struct.Specification.html#method.unicode_encoding
+//! [`UnicodeEncoding`]: struct.UnicodeEncoding.html
+//! [`char`]: https://doc.rust-lang.org/std/primitive.char.html
+//! [`str`]: https://doc.rust-lang.org/std/primitive.str.html
+//! [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+    DecodeError, Encoding, Specification, SpecificationError, SpecificationErrorImpl, Translate,
+    Wrap,
+};
+
+// Internal character used for characters outside the encoding.
+const INVALID: u8 = 127;
+
+/// Encoding with Unicode symbols
+///
+/// See the [module] documentation for more details.
+///
+/// [module]: index.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnicodeEncoding {
+    // Encoding of the internal ASCII characters.
+    encoding: Encoding,
+
+    // Unicode character of each internal ASCII character.
+    chars: Vec<char>,
+
+    // Internal ASCII character of each Unicode character.
+    values: BTreeMap<char, u8>,
+}
+
+impl Specification {
+    /// Returns the specified encoding with Unicode characters
+    ///
+    /// Contrary to [`encoding`], the symbols, padding, ignored characters, wrapping separator, and
+    /// translated characters may be arbitrary characters. Note that the wrapping width is still in
+    /// characters. At most 127 distinct characters can be used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the specification is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::Specification;
+    /// let mut spec = Specification::new();
+    /// spec.symbols.push_str("⠀⠁⠂⠃⠄⠅⠆⠇⠈⠉⠊⠋⠌⠍⠎⠏");
+    /// let braille = spec.unicode_encoding().unwrap();
+    /// assert_eq!(braille.encode(&[0x1f, 0xa0]), "⠁⠏⠊⠀");
+    /// ```
+    ///
+    /// [`encoding`]: ../struct.Specification.html#method.encoding
+    pub fn unicode_encoding(&self) -> Result<UnicodeEncoding, SpecificationError> {
+        let mut chars = Vec::new();
+        let mut values = BTreeMap::new();
+        let mut map = |string: &str| -> Result<String, SpecificationError> {
+            let mut result = String::with_capacity(string.len());
+            for c in string.chars() {
+                let value = match values.get(&c) {
+                    Some(&value) => value,
+                    None => {
+                        if chars.len() == INVALID as usize {
+                            return Err(SpecificationError(
+                                SpecificationErrorImpl::TooManyCharacters,
+                            ));
+                        }
+                        #[allow(clippy::cast_possible_truncation)] // no truncation
+                        let value = chars.len() as u8;
+                        chars.push(c);
+                        let _ = values.insert(c, value);
+                        value
+                    }
+                };
+                result.push(char::from(value));
+            }
+            Ok(result)
+        };
+        let mut padding = [0; 4];
+        let spec = Specification {
+            symbols: map(&self.symbols)?,
+            bit_order: self.bit_order,
+            check_trailing_bits: self.check_trailing_bits,
+            padding: match self.padding {
+                None => None,
+                Some(pad) => map(pad.encode_utf8(&mut padding))?.pop(),
+            },
+            ignore: map(&self.ignore)?,
+            wrap: Wrap { width: self.wrap.width, separator: map(&self.wrap.separator)? },
+            translate: Translate { from: map(&self.translate.from)?, to: map(&self.translate.to)? },
+        };
+        let encoding = spec.encoding().map_err(|error| {
+            SpecificationError(match error.0 {
+                SpecificationErrorImpl::Duplicate(c) => {
+                    SpecificationErrorImpl::Duplicate(chars[c as usize])
+                }
+                SpecificationErrorImpl::Undefined(c) => {
+                    SpecificationErrorImpl::Undefined(chars[c as usize])
+                }
+                x => x,
+            })
+        })?;
+        Ok(UnicodeEncoding { encoding, chars, values })
+    }
+}
+
+impl UnicodeEncoding {
+    fn unmap(&self, string: &str) -> String {
+        string.chars().map(|c| self.chars[c as usize]).collect()
+    }
+
+    /// Returns the encoded length (in characters) of an input of length `len`
+    ///
+    /// See [`Encoding::encode_len`] for more details.
+    ///
+    /// [`Encoding::encode_len`]: ../struct.Encoding.html#method.encode_len
+    #[must_use]
+    pub fn encode_len(&self, len: usize) -> usize {
+        self.encoding.encode_len(len)
+    }
+
+    /// Appends the encoding of `input` to `output`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::Specification;
+    /// let mut spec = Specification::new();
+    /// spec.symbols.push_str("○●");
+    /// let binary = spec.unicode_encoding().unwrap();
+    /// let mut output = String::from("0b");
+    /// binary.encode_append(&[0x5a], &mut output);
+    /// assert_eq!(output, "0b○●○●●○●○");
+    /// ```
+    pub fn encode_append(&self, input: &[u8], output: &mut String) {
+        let encoded = self.encoding.encode(input);
+        output.extend(encoded.bytes().map(|x| self.chars[x as usize]));
+    }
+
+    /// Returns encoded `input`
+    ///
+    /// See [`Encoding::encode`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::Specification;
+    /// let mut spec = Specification::new();
+    /// spec.symbols.push_str("○●");
+    /// let binary = spec.unicode_encoding().unwrap();
+    /// assert_eq!(binary.encode(&[0x5a]), "○●○●●○●○");
+    /// ```
+    ///
+    /// [`Encoding::encode`]: ../struct.Encoding.html#method.encode
+    #[must_use]
+    pub fn encode(&self, input: &[u8]) -> String {
+        let mut output = String::new();
+        self.encode_append(input, &mut output);
+        output
+    }
+
+    /// Returns the maximum decoded length of an input of `len` characters
+    ///
+    /// See [`Encoding::decode_len`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `len` is invalid. The error [position] is in characters.
+    ///
+    /// [`Encoding::decode_len`]: ../struct.Encoding.html#method.decode_len
+    /// [position]: ../struct.DecodeError.html#structfield.position
+    pub fn decode_len(&self, len: usize) -> Result<usize, DecodeError> {
+        self.encoding.decode_len(len)
+    }
+
+    /// Returns decoded `input`
+    ///
+    /// See [`Encoding::decode`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is invalid. The error [position] is a byte offset in `input`
+    /// (always at a character boundary).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use data_encoding::{DecodeKind, Specification};
+    /// let mut spec = Specification::new();
+    /// spec.symbols.push_str("○●");
+    /// spec.ignore.push_str(" ");
+    /// let binary = spec.unicode_encoding().unwrap();
+    /// assert_eq!(binary.decode("○●○● ●○●○").unwrap(), [0x5a]);
+    /// let error = binary.decode("○●○●x●○●○").unwrap_err();
+    /// assert_eq!(error.position, 12);
+    /// assert_eq!(error.kind, DecodeKind::Symbol);
+    /// ```
+    ///
+    /// [`Encoding::decode`]: ../struct.Encoding.html#method.decode
+    /// [position]: ../struct.DecodeError.html#structfield.position
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        let internal: Vec<u8> =
+            input.chars().map(|c| self.values.get(&c).copied().unwrap_or(INVALID)).collect();
+        self.encoding.decode(&internal).map_err(|mut error| {
+            error.position = match input.char_indices().nth(error.position) {
+                Some((position, _)) => position,
+                None => input.len(),
+            };
+            error
+        })
+    }
+
+    /// Returns the bit-width
+    #[must_use]
+    pub fn bit_width(&self) -> usize {
+        self.encoding.bit_width()
+    }
+
+    /// Returns the encoding specification
+    #[must_use]
+    pub fn specification(&self) -> Specification {
+        let spec = self.encoding.specification();
+        Specification {
+            symbols: self.unmap(&spec.symbols),
+            bit_order: spec.bit_order,
+            check_trailing_bits: spec.check_trailing_bits,
+            padding: spec.padding.map(|c| self.chars[c as usize]),
+            ignore: self.unmap(&spec.ignore),
+            wrap: Wrap { width: spec.wrap.width, separator: self.unmap(&spec.wrap.separator) },
+            translate: Translate {
+                from: self.unmap(&spec.translate.from),
+                to: self.unmap(&spec.translate.to),
+            },
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Base45 tests
+
+use data_encoding::base45::BASE45;
+use data_encoding::{DecodeError, DecodeKind, DecodePartial};
+
+#[test]
+fn rfc9285() {
+    let vectors: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"AB", "BB8"),
+        (b"Hello!!", "%69 VD92EX0"),
+        (b"base-45", "UJCLQE7W581"),
+        (b"ietf!", "QED8WEX0"),
+        (b"\0", "00"),
+        (b"\xff", "U5"),
+        (b"\0\0", "000"),
+        (b"\xff\xff", "FGW"),
+    ];
+    for &(decoded, encoded) in vectors {
+        assert_eq!(BASE45.encode(decoded), encoded);
+        assert_eq!(BASE45.decode(encoded.as_bytes()).unwrap(), decoded);
+    }
+}
+
+#[test]
+fn round_trip() {
+    let data: Vec<u8> = (0 .. 100).map(|x: u32| (x * 37 + 11) as u8).collect();
+    for len in 0 .. data.len() {
+        let input = &data[.. len];
+        let encoded = BASE45.encode(input);
+        assert_eq!(encoded.len(), BASE45.encode_len(len));
+        assert_eq!(BASE45.decode(encoded.as_bytes()).unwrap(), input);
+    }
+}
+
+#[test]
+fn decode_len() {
+    assert_eq!(BASE45.decode_len(0), Ok(0));
+    assert_eq!(BASE45.decode_len(2), Ok(1));
+    assert_eq!(BASE45.decode_len(3), Ok(2));
+    assert_eq!(BASE45.decode_len(4), Err(DecodeError { position: 3, kind: DecodeKind::Length }));
+    assert_eq!(BASE45.decode_len(5), Ok(3));
+    assert_eq!(BASE45.decode_len(7), Err(DecodeError { position: 6, kind: DecodeKind::Length }));
+}
+
+#[test]
+fn decode_error() {
+    let err = |position, kind| Err(DecodeError { position, kind });
+    assert_eq!(BASE45.decode(b"0"), err(0, DecodeKind::Length));
+    assert_eq!(BASE45.decode(b"QED8"), err(3, DecodeKind::Length));
+    assert_eq!(BASE45.decode(b"qED"), err(0, DecodeKind::Symbol));
+    assert_eq!(BASE45.decode(b"QED8wE"), err(4, DecodeKind::Symbol));
+    assert_eq!(BASE45.decode(b"QED8W="), err(5, DecodeKind::Symbol));
+    assert_eq!(BASE45.decode(b"QEDGGW"), err(5, DecodeKind::Symbol));
+    assert_eq!(BASE45.decode(b"QED::"), err(4, DecodeKind::Symbol));
+    assert_eq!(BASE45.decode(b"QEDV5"), err(4, DecodeKind::Symbol));
+}
+
+#[test]
+fn decode_partial() {
+    let mut output = [0u8; 4];
+    assert_eq!(
+        BASE45.decode_mut(b"QED8W_", &mut output),
+        Err(DecodePartial {
+            read: 3,
+            written: 2,
+            error: DecodeError { position: 5, kind: DecodeKind::Symbol },
+        })
+    );
+    assert_eq!(&output[.. 2], b"ie");
+}
@@ -0,0 +1,114 @@
+//! Base58 tests
+
+use data_encoding::base58::{Base58, BITCOIN, FLICKR, RIPPLE};
+use data_encoding::DecodeError;
+use data_encoding::DecodeKind::*;
+
+#[track_caller]
+fn test(base: &Base58, x: &[u8], y: &str) {
+    assert_eq!(base.encode(x), y);
+    assert_eq!(base.decode(y.as_bytes()).unwrap(), x);
+}
+
+fn hex(x: &str) -> Vec<u8> {
+    data_encoding::HEXLOWER_PERMISSIVE.decode(x.as_bytes()).unwrap()
+}
+
+#[test]
+fn bitcoin() {
+    test(&BITCOIN, b"", "");
+    test(&BITCOIN, b"\0", "1");
+    test(&BITCOIN, b"\0\0\0", "111");
+    test(&BITCOIN, b"a", "2g");
+    test(&BITCOIN, b"bbb", "a3gV");
+    test(&BITCOIN, b"ccc", "aPEr");
+    test(&BITCOIN, b"Hello World!", "2NEpo7TZRRrLZSi2U");
+    test(&BITCOIN, b"simply a long string", "2cFupjhnEsSn59qHXstmK2ffpLv2");
+    test(&BITCOIN, &hex("516b6fcd0f"), "ABnLTmg");
+    test(&BITCOIN, &hex("bf4f89001e670274dd"), "3SEo3LWLoPntC");
+    test(&BITCOIN, &hex("572e4794"), "3EFU7m");
+    test(&BITCOIN, &hex("ecac89cad93923c02321"), "EJDM8drfXA6uyA");
+    test(&BITCOIN, &hex("10c8511e"), "Rt5zm");
+    test(
+        &BITCOIN,
+        &hex("00eb15231dfceb60925886b67d065299925915aeb172c06647"),
+        "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+    );
+    test(
+        &BITCOIN,
+        &hex("000111d38e5fc9071ffcd20b4a763cc9ae4f252bb4e48fd66a835e252ada93ff480d6dd43dc62a641155a5"),
+        "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    );
+}
+
+#[test]
+fn alphabets() {
+    test(&RIPPLE, b"\0\0Hello world", "rrJxErpTiAXzTnjvT");
+    test(&FLICKR, b"\0\0Hello world", "11iXf12sRWwZs5JVs");
+    assert_eq!(RIPPLE.encode(&[0; 3]), "rrr");
+    assert_eq!(FLICKR.decode(b"Z").unwrap(), [57]);
+}
+
+#[test]
+fn round_trip() {
+    for len in 0 .. 40 {
+        for &fill in &[0, 1, 0x7f, 0xff] {
+            let mut input = vec![fill; len];
+            for zeros in 0 .. len.min(3) {
+                input[zeros] = 0;
+                let encoded = BITCOIN.encode(&input);
+                assert!(encoded.len() <= BITCOIN.encode_len(len));
+                assert_eq!(BITCOIN.decode(encoded.as_bytes()).unwrap(), input);
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_error() {
+    let error = |position| DecodeError { position, kind: Symbol };
+    assert_eq!(BITCOIN.decode(b"0"), Err(error(0)));
+    assert_eq!(BITCOIN.decode(b"11O"), Err(error(2)));
+    assert_eq!(BITCOIN.decode(b"2gI2g"), Err(error(2)));
+    assert_eq!(BITCOIN.decode(b"2g\n"), Err(error(2)));
+    assert_eq!(BITCOIN.decode(b"2g\xff"), Err(error(2)));
+    assert_eq!(RIPPLE.decode(b"0"), Err(error(0)));
+}
+
+#[test]
+fn check() {
+    #[track_caller]
+    fn test(x: &[u8], y: &str) {
+        assert_eq!(BITCOIN.encode_check(x), y);
+        assert_eq!(BITCOIN.decode_check(y.as_bytes()).unwrap(), x);
+    }
+    test(&hex("00f54a5851e9372b87810a8e60cdd2e7cfd80b6e31"), "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs");
+    test(&hex("00010966776006953d5567439e5e39f86a0d273bee"), "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+    test(
+        &hex("800c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d"),
+        "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ",
+    );
+    test(b"", "3QJmnh");
+    // This input spans multiple SHA-256 blocks.
+    let input: Vec<u8> = (0 .. 200).map(|i| (i * 7 + 3) as u8).collect();
+    test(
+        &input,
+        "8h2iS4m4bx9ezNpvAVH4NxGNV3KiT6CCLBmzYpeacLf2u2MCastaY5tnRrf9WojCfFdPCwHVRCuKYrzPQ8jkHMAU6ZJUA\
+         UMVf4Ltd4x1mWuwJqbMyU57Pk1FDA4hzwhR7Zq3EQ46GXVV13v2cqW4kxzVX7wacoyP545qvhR491MxsfUEz66KktNePay\
+         A6nQzEbxCN9n33J4cospYFERQbqszDQRwXEXK5hsYsfd6JipEJHZBrDcDXNARj9y5GC37gh2MuLyLP4tnWD6otvF75z",
+    );
+}
+
+#[test]
+fn check_error() {
+    assert_eq!(BITCOIN.decode_check(b""), Err(DecodeError { position: 0, kind: Length }));
+    assert_eq!(BITCOIN.decode_check(b"2g"), Err(DecodeError { position: 2, kind: Length }));
+    assert_eq!(
+        BITCOIN.decode_check(b"1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt"),
+        Err(DecodeError { position: 33, kind: Trailing })
+    );
+    assert_eq!(
+        BITCOIN.decode_check(b"1PMycacnJaSqwwJqjawXBErnLsZ7RkXUA0"),
+        Err(DecodeError { position: 33, kind: Symbol })
+    );
+}
@@ -0,0 +1,106 @@
+//! Base85 tests
+
+use data_encoding::base85::{Base85, ASCII85, RFC1924, Z85};
+use data_encoding::DecodeError;
+use data_encoding::DecodeKind::*;
+
+#[track_caller]
+fn test(base: &Base85, x: &[u8], y: &str) {
+    assert_eq!(base.encode(x), y);
+    assert_eq!(base.decode(y.as_bytes()).unwrap(), x);
+}
+
+#[test]
+fn ascii85() {
+    test(&ASCII85, b"", "<~~>");
+    test(&ASCII85, b"\0", "<~!!~>");
+    test(&ASCII85, b"abc", "<~@:E^~>");
+    test(&ASCII85, b"\0\0\0\0", "<~z~>");
+    test(&ASCII85, b"\0\0\0\0\0", "<~z!!~>");
+    test(&ASCII85, b"\xff\xff\xff\xff", "<~s8W-!~>");
+    test(&ASCII85, b"Hello world", "<~87cURD]j7BEbo7~>");
+    test(&ASCII85, b"Man is distinguished", "<~9jqo^BlbD-BleB1DJ+*+F(f,q~>");
+    assert_eq!(
+        ASCII85.decode(b"<~9jqo^ Blb\r\nD-BleB1\tDJ+*+F(f,q\n~>").unwrap(),
+        b"Man is distinguished"
+    );
+    assert_eq!(ASCII85.decode(b"<~9jqo^zz~>").unwrap(), b"Man \0\0\0\0\0\0\0\0");
+}
+
+#[test]
+fn z85() {
+    test(&Z85, &[0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b], "HelloWorld");
+    test(&Z85, b"", "");
+    test(&Z85, b"\0\0\0\0\0", "0000000");
+    test(&Z85, b"\xff\xff\xff\xff", "%nSc0");
+    test(&Z85, b"Hello world", "nm=QNzY<mxA+]m");
+}
+
+#[test]
+fn rfc1924() {
+    test(&RFC1924, b"", "");
+    test(&RFC1924, b"abc", "VPaz");
+    test(&RFC1924, b"\xff\xff\xff\xff", "|NsC0");
+    test(&RFC1924, b"Hello world", "NM&qnZy<MXa%^M");
+    test(&RFC1924, b"Man is distinguished", "O<`^zX>%ZCX>)XGZfA9Ab7*B`");
+}
+
+#[test]
+fn round_trip() {
+    for base in &[ASCII85, Z85, RFC1924] {
+        for len in 0 .. 20 {
+            for &fill in &[0, 1, 0x80, 0xff] {
+                let input = vec![fill; len];
+                let encoded = base.encode(&input);
+                assert!(encoded.len() <= base.encode_len(len));
+                let decoded = base.decode(encoded.as_bytes()).unwrap();
+                assert_eq!(decoded, input);
+                assert!(decoded.len() <= base.decode_len(encoded.len()).unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_len() {
+    assert_eq!(Z85.decode_len(0), Ok(0));
+    assert_eq!(Z85.decode_len(1), Err(DecodeError { position: 0, kind: Length }));
+    assert_eq!(Z85.decode_len(2), Ok(1));
+    assert_eq!(Z85.decode_len(5), Ok(4));
+    assert_eq!(Z85.decode_len(6), Err(DecodeError { position: 5, kind: Length }));
+    assert_eq!(Z85.decode_len(9), Ok(7));
+    assert_eq!(ASCII85.decode_len(6), Ok(16));
+}
+
+#[test]
+fn decode_error() {
+    let error = |position, kind| Err(DecodeError { position, kind });
+    assert_eq!(Z85.decode(b"Hello~orld"), error(5, Symbol));
+    assert_eq!(Z85.decode(b"Hel loWorl"), error(3, Symbol));
+    assert_eq!(Z85.decode(b"%nSc1"), error(0, Symbol));
+    assert_eq!(Z85.decode(b"#####"), error(0, Symbol));
+    assert_eq!(Z85.decode(b"%nSc0#"), error(5, Length));
+    assert_eq!(Z85.decode(b"01"), error(1, Trailing));
+    assert_eq!(Z85.decode(b"##"), error(0, Symbol));
+    assert_eq!(RFC1924.decode(b"VPa{"), error(3, Trailing));
+    assert_eq!(ASCII85.decode(b""), error(0, Length));
+    assert_eq!(ASCII85.decode(b"<"), error(1, Length));
+    assert_eq!(ASCII85.decode(b"<!"), error(1, Symbol));
+    assert_eq!(ASCII85.decode(b"~>"), error(0, Symbol));
+    assert_eq!(ASCII85.decode(b"<~!!"), error(4, Length));
+    assert_eq!(ASCII85.decode(b"<~!!~"), error(5, Length));
+    assert_eq!(ASCII85.decode(b"<~!!~~"), error(5, Symbol));
+    assert_eq!(ASCII85.decode(b"<~!!~>!"), error(6, Symbol));
+    assert_eq!(ASCII85.decode(b"<~!z~>"), error(3, Symbol));
+    assert_eq!(ASCII85.decode(b"<~!v~>"), error(3, Symbol));
+    assert_eq!(ASCII85.decode(b"<~! ~>"), error(2, Length));
+    assert_eq!(ASCII85.decode(b"<~!#~>"), error(3, Trailing));
+}
+
+#[test]
+fn decode_partial() {
+    let mut output = [0; 12];
+    let partial = Z85.decode_mut(b"HelloWorld#####", &mut output).unwrap_err();
+    assert_eq!((partial.read, partial.written), (10, 8));
+    assert_eq!(partial.error, DecodeError { position: 10, kind: Symbol });
+}
@@ -0,0 +1,155 @@
+//! Bech32 tests
+
+use data_encoding::bech32::{decode, encode, Error, ErrorKind, Variant};
+use data_encoding::Specification;
+
+#[track_caller]
+fn error(input: &[u8], variant: Variant, position: usize, kind: ErrorKind) {
+    assert_eq!(decode(input, variant), Err(Error { position, kind }));
+}
+
+#[test]
+fn bip173_valid() {
+    let vectors = [
+        "A12UEL5L",
+        "a12uel5l",
+        "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+        "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+        "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+        "?1ezyfcl",
+    ];
+    for input in &vectors {
+        let (hrp, data) = decode(input.as_bytes(), Variant::Bech32).unwrap();
+        assert_eq!(encode(&hrp, &data, Variant::Bech32).unwrap(), input.to_lowercase());
+        let position = input.len() - 6;
+        error(input.as_bytes(), Variant::Bech32m, position, ErrorKind::Checksum);
+    }
+}
+
+#[test]
+fn bip350_valid() {
+    let vectors = [
+        "A1LQFN3A",
+        "a1lqfn3a",
+        "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+        "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+        "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+        "?1v759aa",
+    ];
+    for input in &vectors {
+        let (hrp, data) = decode(input.as_bytes(), Variant::Bech32m).unwrap();
+        assert_eq!(encode(&hrp, &data, Variant::Bech32m).unwrap(), input.to_lowercase());
+        let position = input.len() - 6;
+        error(input.as_bytes(), Variant::Bech32, position, ErrorKind::Checksum);
+    }
+    // The checksum is valid but the data has non-zero trailing bits.
+    let input = "11llllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllludsr8";
+    error(input.as_bytes(), Variant::Bech32m, 83, ErrorKind::Padding);
+}
+
+#[test]
+fn bip173_invalid() {
+    let b = Variant::Bech32;
+    error(b"\x201nwldj5", b, 0, ErrorKind::Hrp);
+    error(b"\x7f1axkwrx", b, 0, ErrorKind::Hrp);
+    error(b"\x801eym55h", b, 0, ErrorKind::Hrp);
+    let input = b"an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx";
+    error(input, b, 90, ErrorKind::Length);
+    error(b"pzry9x0s0muk", b, 12, ErrorKind::Separator);
+    error(b"1pzry9x0s0muk", b, 0, ErrorKind::Hrp);
+    error(b"x1b4n0q5v", b, 2, ErrorKind::Symbol);
+    error(b"li1dgmt3", b, 8, ErrorKind::Length);
+    error(b"de1lg7wt\xff", b, 8, ErrorKind::Symbol);
+    error(b"A1G7SGD8", b, 2, ErrorKind::Checksum);
+    error(b"10a06t8", b, 0, ErrorKind::Hrp);
+    error(b"1qzzfhee", b, 0, ErrorKind::Hrp);
+}
+
+#[test]
+fn bip350_invalid() {
+    let m = Variant::Bech32m;
+    error(b"\x201xj0phk", m, 0, ErrorKind::Hrp);
+    error(b"qyrz8wqd2c9m", m, 12, ErrorKind::Separator);
+    error(b"1qyrz8wqd2c9m", m, 0, ErrorKind::Hrp);
+    error(b"y1b0jsk6g", m, 2, ErrorKind::Symbol);
+    error(b"lt1igcx5c0", m, 3, ErrorKind::Symbol);
+    error(b"in1muywd", m, 8, ErrorKind::Length);
+    error(b"mm1crxm3i", m, 8, ErrorKind::Symbol);
+    error(b"au1s5cgom", m, 7, ErrorKind::Symbol);
+    error(b"M1VUXWEZ", m, 2, ErrorKind::Checksum);
+    error(b"16plkw9", m, 0, ErrorKind::Hrp);
+    error(b"1p2gdwpf", m, 0, ErrorKind::Hrp);
+}
+
+#[test]
+fn mixed_case() {
+    error(b"A12uel5l", Variant::Bech32, 3, ErrorKind::MixedCase);
+    error(b"a12UEL5L", Variant::Bech32, 3, ErrorKind::MixedCase);
+    error(b"aB12uel5l", Variant::Bech32, 1, ErrorKind::MixedCase);
+    assert_eq!(
+        encode("aB", b"", Variant::Bech32),
+        Err(Error { position: 1, kind: ErrorKind::MixedCase })
+    );
+}
+
+#[test]
+fn round_trip() {
+    assert_eq!(encode("hello", b"world", Variant::Bech32).unwrap(), "hello1wahhymrynlvsms");
+    assert_eq!(encode("hello", b"world", Variant::Bech32m).unwrap(), "hello1wahhymryxruu7j");
+    let data: Vec<u8> = (0 .. 20).collect();
+    let encoded = "bc1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnt4wzqr";
+    assert_eq!(encode("bc", &data, Variant::Bech32).unwrap(), encoded);
+    assert_eq!(decode(encoded.as_bytes(), Variant::Bech32).unwrap(), ("bc".to_string(), data));
+    assert_eq!(encode("abc", &[0xff; 7], Variant::Bech32m).unwrap(), "abc1lllllllllllsqf45j4");
+    for len in 0 .. 50 {
+        let data: Vec<u8> = (0 .. len).map(|x: u8| x.wrapping_mul(73) ^ 5).collect();
+        for &variant in &[Variant::Bech32, Variant::Bech32m] {
+            let encoded = encode("test", &data, variant).unwrap();
+            assert_eq!(
+                decode(encoded.as_bytes(), variant).unwrap(),
+                ("test".to_string(), data.clone())
+            );
+        }
+    }
+}
+
+#[test]
+fn data_specification() {
+    // The data part must behave as the encoding described next to its static table.
+    let mut spec = Specification::new();
+    spec.symbols.push_str("qpzry9x8gf2tvdw0s3jn54khce6mua7l");
+    let base = spec.encoding().unwrap();
+    let data: Vec<u8> = (0 ..= 255).collect();
+    for chunk in data.chunks(41) {
+        for len in 0 ..= chunk.len() {
+            let encoded = encode("a", &chunk[.. len], Variant::Bech32).unwrap();
+            assert_eq!(&encoded[2 .. encoded.len() - 6], base.encode(&chunk[.. len]));
+            let (_, decoded) = decode(encoded.as_bytes(), Variant::Bech32).unwrap();
+            assert_eq!(decoded, &chunk[.. len]);
+        }
+    }
+    for x in 0 ..= 255 {
+        if x == b'1' {
+            continue;
+        }
+        let input = [b'a', b'1', x, b'q', b'q', b'q', b'q', b'q', b'q'];
+        let symbol = decode(&input, Variant::Bech32).unwrap_err().kind == ErrorKind::Symbol;
+        assert_eq!(symbol, base.decode(&[x.to_ascii_lowercase(), b'q']).is_err(), "{}", x);
+    }
+    // Both reject non-zero trailing bits.
+    assert_eq!(base.decode(b"ll").unwrap_err().kind, data_encoding::DecodeKind::Trailing);
+    error(b"a1llttal5m", Variant::Bech32, 3, ErrorKind::Padding);
+}
+
+#[test]
+fn encode_error() {
+    let hrp = |position| Err(Error { position, kind: ErrorKind::Hrp });
+    assert_eq!(encode("", b"", Variant::Bech32), hrp(0));
+    assert_eq!(encode("a b", b"", Variant::Bech32), hrp(1));
+    assert_eq!(encode("é", b"", Variant::Bech32), hrp(0));
+    let length = Err(Error { position: 90, kind: ErrorKind::Length });
+    assert_eq!(encode("a", &[0; 51], Variant::Bech32).map(|x| x.len()), Ok(90));
+    assert_eq!(encode("ab", &[0; 51], Variant::Bech32), length);
+    assert_eq!(encode(&"a".repeat(84), b"", Variant::Bech32), length);
+}
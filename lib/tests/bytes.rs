@@ -0,0 +1,146 @@
+//! Bytes tests
+
+#![cfg(feature = "bytes")]
+
+use bytes::{Buf, BufMut, BytesMut};
+use data_encoding::{Encoding, Specification, BASE32, BASE64, BASE64_MIME, HEXLOWER};
+
+// Buffer made of the given chunks.
+struct Chunks<'a> {
+    chunks: Vec<&'a [u8]>,
+}
+
+impl<'a> Chunks<'a> {
+    // Splits `input` in chunks of the given lengths (and a last chunk with the rest).
+    fn new(mut input: &'a [u8], lens: &[usize]) -> Self {
+        let mut chunks = Vec::new();
+        for &len in lens {
+            let len = std::cmp::min(len, input.len());
+            chunks.push(&input[.. len]);
+            input = &input[len ..];
+        }
+        chunks.push(input);
+        chunks.retain(|x| !x.is_empty());
+        Chunks { chunks }
+    }
+}
+
+impl Buf for Chunks<'_> {
+    fn remaining(&self) -> usize {
+        self.chunks.iter().map(|x| x.len()).sum()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.chunks.first().copied().unwrap_or(&[])
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        while cnt > 0 {
+            let len = std::cmp::min(cnt, self.chunks[0].len());
+            self.chunks[0] = &self.chunks[0][len ..];
+            if self.chunks[0].is_empty() {
+                let _ = self.chunks.remove(0);
+            }
+            cnt -= len;
+        }
+    }
+}
+
+fn splits() -> Vec<Vec<usize>> {
+    vec![
+        vec![],
+        vec![1; 100],
+        vec![2, 3, 5, 7, 11, 13, 17],
+        vec![0, 4, 0, 8],
+        vec![5000],
+        vec![4095, 4097, 1],
+        vec![77, 1, 300],
+    ]
+}
+
+fn encodings() -> Vec<Encoding> {
+    let mut spec = Specification::new();
+    spec.symbols.push_str("01234567");
+    spec.padding = Some('=');
+    spec.ignore.push_str(" \n");
+    spec.wrap.width = 16;
+    spec.wrap.separator.push_str(" \n");
+    let octal = spec.encoding().unwrap();
+    vec![HEXLOWER, BASE32, BASE64, BASE64_MIME, octal]
+}
+
+#[test]
+fn encode_buf() {
+    let data: Vec<u8> = (0 .. 10000u32).map(|x| (x * 7 + x / 13) as u8).collect();
+    for encoding in &encodings() {
+        for &len in &[0, 1, 2, 3, 57, 100, 4096, 10000] {
+            let expected = encoding.encode(&data[.. len]);
+            for lens in &splits() {
+                let mut output = BytesMut::new();
+                encoding.encode_buf(Chunks::new(&data[.. len], lens), &mut output);
+                assert_eq!(output, expected.as_bytes());
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_buf() {
+    let data: Vec<u8> = (0 .. 10000u32).map(|x| (x * 7 + x / 13) as u8).collect();
+    for encoding in &encodings() {
+        for &len in &[0, 1, 2, 3, 57, 100, 4096, 10000] {
+            let input = encoding.encode(&data[.. len]);
+            for lens in &splits() {
+                let mut output = BytesMut::new();
+                let result = encoding.decode_buf(Chunks::new(input.as_bytes(), lens), &mut output);
+                assert_eq!(result, Ok(len));
+                assert_eq!(output, &data[.. len]);
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_buf_concatenated() {
+    let input = b"SGVs\nbG8=\n IHdv\ncmxk";
+    let mut spec = BASE64.specification();
+    spec.ignore.push_str(" \n");
+    let base64 = spec.encoding().unwrap();
+    for lens in &splits() {
+        let mut output = Vec::new();
+        let result = base64.decode_buf(Chunks::new(input, lens), &mut output);
+        assert_eq!(result, Ok(11));
+        assert_eq!(output, b"Hello world");
+    }
+}
+
+#[test]
+fn decode_buf_error() {
+    let mut spec = BASE64.specification();
+    spec.ignore.push_str(" \n");
+    let base64 = spec.encoding().unwrap();
+    let valid = base64.encode(&[0xa5; 300]);
+    for &(position, x) in &[(0, b'!'), (5, b'='), (399, b'A'), (200, b'A'), (4, b'A')] {
+        let mut input = valid.clone().into_bytes();
+        input.insert(position, x);
+        let mut decoded = vec![0; base64.decode_len(input.len()).unwrap()];
+        let partial = base64.decode_mut(&input, &mut decoded).unwrap_err();
+        let expected = partial.error;
+        for lens in &splits() {
+            let mut output = Vec::new();
+            let error = base64.decode_buf(Chunks::new(&input, lens), &mut output).unwrap_err();
+            assert_eq!(error, expected);
+            assert!(output.len() <= partial.written);
+            assert_eq!(output[..], decoded[.. output.len()]);
+        }
+    }
+}
+
+#[test]
+fn buf_mut_limit() {
+    let mut buffer = [0u8; 16];
+    let mut output = (&mut buffer[..]).limit(8);
+    BASE64.encode_buf(&b"hello"[..], &mut output);
+    assert_eq!(output.remaining_mut(), 0);
+    assert_eq!(&buffer[.. 8], b"aGVsbG8=");
+}
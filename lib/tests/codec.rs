@@ -0,0 +1,122 @@
+//! Codec tests
+
+#![cfg(all(feature = "bytes", feature = "tokio-util"))]
+
+use bytes::BytesMut;
+use data_encoding::codec::{LineCodec, LineCodecError};
+use data_encoding::{DecodeError, DecodeKind, Encoding, BASE64, BASE64_MIME, HEXLOWER};
+use tokio_util::codec::{Decoder, Encoder};
+
+static BASE64_STATIC: Encoding = BASE64;
+static HEXLOWER_STATIC: Encoding = HEXLOWER;
+static BASE64_MIME_STATIC: Encoding = BASE64_MIME;
+
+type Frame = Result<Vec<u8>, String>;
+
+// Decodes `input` fed in chunks of `size` bytes, and returns all frames (and errors).
+fn decode(mut codec: LineCodec, input: &[u8], size: usize) -> Vec<Frame> {
+    let mut frames = Vec::new();
+    let mut buffer = BytesMut::new();
+    let mut push = |result: Result<Option<Vec<u8>>, LineCodecError>| match result {
+        Ok(None) => false,
+        Ok(Some(frame)) => {
+            frames.push(Ok(frame));
+            true
+        }
+        Err(error) => {
+            frames.push(Err(error.to_string()));
+            true
+        }
+    };
+    for chunk in input.chunks(size) {
+        buffer.extend_from_slice(chunk);
+        while push(codec.decode(&mut buffer)) {}
+    }
+    while push(codec.decode_eof(&mut buffer)) {}
+    frames
+}
+
+#[test]
+fn decode_lines() {
+    let input = b"aGVsbG8=\r\nd29ybGQ=\r\n\r\nISE=";
+    let expected: Vec<Frame> =
+        vec![Ok(b"hello".to_vec()), Ok(b"world".to_vec()), Ok(vec![]), Ok(b"!!".to_vec())];
+    for size in 1 ..= input.len() {
+        let codec = LineCodec::new(&BASE64_STATIC).separator(b"\r\n");
+        assert_eq!(decode(codec, input, size), expected);
+    }
+}
+
+#[test]
+fn decode_errors() {
+    let input = b"6869\n0123456789abcdef\n68x9\n21\n0123456789abcdef0123";
+    let expected: Vec<Frame> = vec![
+        Ok(b"hi".to_vec()),
+        Err("line too long".to_string()),
+        Err("invalid line: invalid symbol at 2".to_string()),
+        Ok(b"!".to_vec()),
+        Err("line too long".to_string()),
+    ];
+    for size in 1 ..= input.len() {
+        let codec = LineCodec::new(&HEXLOWER_STATIC).max_length(8);
+        assert_eq!(decode(codec, input, size), expected);
+    }
+}
+
+#[test]
+fn decode_error_kind() {
+    let mut codec = LineCodec::new(&HEXLOWER_STATIC);
+    let mut buffer = BytesMut::from(&b"686\n"[..]);
+    match codec.decode(&mut buffer) {
+        Err(LineCodecError::Decode(error)) => {
+            assert_eq!(error, DecodeError { position: 2, kind: DecodeKind::Length });
+        }
+        result => panic!("unexpected {:?}", result),
+    }
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn encode() {
+    let mut codec = LineCodec::new(&BASE64_STATIC).max_length(8);
+    let mut buffer = BytesMut::new();
+    codec.encode(b"hello", &mut buffer).unwrap();
+    codec.encode(Vec::new(), &mut buffer).unwrap();
+    codec.encode(&b"world"[..], &mut buffer).unwrap();
+    assert!(matches!(codec.encode(b"hello world", &mut buffer), Err(LineCodecError::MaxLength)));
+    assert_eq!(buffer, &b"aGVsbG8=\n\nd29ybGQ=\n"[..]);
+    assert_eq!(
+        decode(codec, &buffer, 3),
+        vec![Ok(b"hello".to_vec()), Ok(vec![]), Ok(b"world".to_vec())]
+    );
+}
+
+// Returns BASE64 wrapped every 4 characters with a space.
+fn wrapped() -> &'static Encoding {
+    let mut spec = BASE64.specification();
+    spec.wrap.width = 4;
+    spec.wrap.separator.push(' ');
+    Box::leak(Box::new(spec.encoding().unwrap()))
+}
+
+#[test]
+fn encode_wrapped() {
+    let mut codec = LineCodec::new(wrapped());
+    let mut buffer = BytesMut::new();
+    codec.encode(b"hello", &mut buffer).unwrap();
+    codec.encode(b"world", &mut buffer).unwrap();
+    assert_eq!(buffer, &b"aGVs bG8= \nd29y bGQ= \n"[..]);
+    assert_eq!(decode(codec, &buffer, 3), vec![Ok(b"hello".to_vec()), Ok(b"world".to_vec())]);
+}
+
+#[test]
+#[should_panic(expected = "separator must not appear in wrapped lines")]
+fn new_wrapped() {
+    drop(LineCodec::new(&BASE64_MIME_STATIC));
+}
+
+#[test]
+#[should_panic(expected = "separator must not appear in wrapped lines")]
+fn separator_wrapped() {
+    drop(LineCodec::new(wrapped()).separator(b" "));
+}
@@ -0,0 +1,71 @@
+//! Detection tests
+
+use data_encoding::{detect, Encoding, BASE32, BASE32_DNSCURVE, BASE64_MIME, HEXUPPER};
+
+fn names(input: &[u8]) -> Vec<&'static str> {
+    detect(input).iter().map(|x| x.0).collect()
+}
+
+#[test]
+fn empty() {
+    assert_eq!(detect(b"").len(), 18);
+}
+
+#[test]
+fn round_trip() {
+    let data: Vec<u8> = (0 .. 100u8).map(|x| x.wrapping_mul(37)).collect();
+    for &(name, encoding) in &detect(b"") {
+        for len in 0 .. data.len() {
+            let encoded = encoding.encode(&data[.. len]);
+            let detected = detect(encoded.as_bytes());
+            assert!(detected.contains(&(name, encoding)), "{} {}", name, len);
+        }
+    }
+}
+
+#[test]
+fn ranking() {
+    assert_eq!(
+        names(b"DEADBEEF")[.. 4],
+        ["HEXUPPER", "HEXUPPER_PERMISSIVE", "BASE32", "BASE32_NOPAD"]
+    );
+    assert_eq!(
+        names(b"JBSWY3DP"),
+        [
+            "BASE32",
+            "BASE32_NOPAD",
+            "BASE32_NOPAD_NOCASE",
+            "BASE32_NOPAD_VISUAL",
+            "BASE64",
+            "BASE64_NOPAD",
+            "BASE64URL",
+            "BASE64URL_NOPAD"
+        ]
+    );
+    assert_eq!(names(b"JBSWY3DPEE======"), ["BASE32"]);
+    assert_eq!(names(b"-_-_"), ["BASE64URL", "BASE64URL_NOPAD"]);
+}
+
+#[test]
+fn canonical() {
+    // Non-zero trailing bits.
+    assert!(names(b"SGk=").contains(&"BASE64"));
+    assert!(!names(b"SGl=").contains(&"BASE64"));
+    // Translated characters.
+    assert!(names(b"deadbeef").contains(&"HEXLOWER_PERMISSIVE"));
+    assert!(!names(b"deadBEEF").contains(&"HEXLOWER_PERMISSIVE"));
+    // Ignored characters.
+    let encoded = BASE64_MIME.encode(&[0; 100]);
+    assert_eq!(names(encoded.as_bytes()), ["BASE64_MIME", "BASE64_MIME_PERMISSIVE"]);
+    assert!(names(encoded.replace("\r\n", "\n").as_bytes()).is_empty());
+}
+
+#[test]
+fn encodings() {
+    let get = |input: &[u8], name: &str| -> Option<&'static Encoding> {
+        detect(input).iter().find(|x| x.0 == name).map(|x| x.1)
+    };
+    assert_eq!(get(b"48454C4C4F", "HEXUPPER"), Some(&HEXUPPER));
+    assert_eq!(get(b"JBSWY3DP", "BASE32"), Some(&BASE32));
+    assert_eq!(get(b"jbswy3dp", "BASE32_DNSCURVE"), Some(&BASE32_DNSCURVE));
+}
@@ -0,0 +1,214 @@
+//! Hexdump tests
+
+use data_encoding::hexdump::{parse, parse_with_limit, Format, HexDump};
+use data_encoding::{DecodeError, DecodeKind};
+
+const INPUT: &[u8] = b"\x00\x05\x0a\x0f\x14\x19\x1e#(-27<AFKPUZ_dinsx}\x82\x87\x8c\x91\x96\x9b\xa0\xa5\xaa\xaf\xb4\xb9\xbe\xc3\xc8\xcd\xd2\xd7\xdc\xe1\xe6\xeb\xf0\xf5\xfa\xffThe quick brown fox.";
+
+// The expected outputs have been generated with xxd.
+#[test]
+fn xxd() {
+    // xxd
+    assert_eq!(
+        HexDump::new(INPUT).to_string(),
+        r#"00000000: 0005 0a0f 1419 1e23 282d 3237 3c41 464b  .......#(-27<AFK
+00000010: 5055 5a5f 6469 6e73 787d 8287 8c91 969b  PUZ_dinsx}......
+00000020: a0a5 aaaf b4b9 bec3 c8cd d2d7 dce1 e6eb  ................
+00000030: f0f5 faff 5468 6520 7175 6963 6b20 6272  ....The quick br
+00000040: 6f77 6e20 666f 782e                      own fox.
+"#
+    );
+    // xxd -c 10 -g 4 -o 4096
+    assert_eq!(
+        HexDump::new(INPUT).width(10).group(4).offset(4096).to_string(),
+        r#"00001000: 00050a0f 14191e23 282d  .......#(-
+0000100a: 32373c41 464b5055 5a5f  27<AFKPUZ_
+00001014: 64696e73 787d8287 8c91  dinsx}....
+0000101e: 969ba0a5 aaafb4b9 bec3  ..........
+00001028: c8cdd2d7 dce1e6eb f0f5  ..........
+00001032: faff5468 65207175 6963  ..The quic
+0000103c: 6b206272 6f776e20 666f  k brown fo
+00001046: 782e                    x.
+"#
+    );
+    // xxd -g 0
+    assert_eq!(
+        HexDump::new(INPUT).group(0).to_string(),
+        r#"00000000: 00050a0f14191e23282d32373c41464b  .......#(-27<AFK
+00000010: 50555a5f64696e73787d82878c91969b  PUZ_dinsx}......
+00000020: a0a5aaafb4b9bec3c8cdd2d7dce1e6eb  ................
+00000030: f0f5faff54686520717569636b206272  ....The quick br
+00000040: 6f776e20666f782e                  own fox.
+"#
+    );
+    // xxd -g 1 -c 8
+    assert_eq!(
+        HexDump::new(INPUT).group(1).width(8).to_string(),
+        r#"00000000: 00 05 0a 0f 14 19 1e 23  .......#
+00000008: 28 2d 32 37 3c 41 46 4b  (-27<AFK
+00000010: 50 55 5a 5f 64 69 6e 73  PUZ_dins
+00000018: 78 7d 82 87 8c 91 96 9b  x}......
+00000020: a0 a5 aa af b4 b9 be c3  ........
+00000028: c8 cd d2 d7 dc e1 e6 eb  ........
+00000030: f0 f5 fa ff 54 68 65 20  ....The 
+00000038: 71 75 69 63 6b 20 62 72  quick br
+00000040: 6f 77 6e 20 66 6f 78 2e  own fox.
+"#
+    );
+    // xxd -c 7 -g 3
+    assert_eq!(
+        HexDump::new(INPUT).width(7).group(3).to_string(),
+        r#"00000000: 00050a 0f1419 1e  .......
+00000007: 23282d 32373c 41  #(-27<A
+0000000e: 464b50 555a5f 64  FKPUZ_d
+00000015: 696e73 787d82 87  insx}..
+0000001c: 8c9196 9ba0a5 aa  .......
+00000023: afb4b9 bec3c8 cd  .......
+0000002a: d2d7dc e1e6eb f0  .......
+00000031: f5faff 546865 20  ...The 
+00000038: 717569 636b20 62  quick b
+0000003f: 726f77 6e2066 6f  rown fo
+00000046: 782e              x.
+"#
+    );
+    // xxd -c 4 -g 8
+    assert_eq!(
+        HexDump::new(INPUT).width(4).group(8).to_string(),
+        r#"00000000: 00050a0f  ....
+00000004: 14191e23  ...#
+00000008: 282d3237  (-27
+0000000c: 3c41464b  <AFK
+00000010: 50555a5f  PUZ_
+00000014: 64696e73  dins
+00000018: 787d8287  x}..
+0000001c: 8c91969b  ....
+00000020: a0a5aaaf  ....
+00000024: b4b9bec3  ....
+00000028: c8cdd2d7  ....
+0000002c: dce1e6eb  ....
+00000030: f0f5faff  ....
+00000034: 54686520  The 
+00000038: 71756963  quic
+0000003c: 6b206272  k br
+00000040: 6f776e20  own 
+00000044: 666f782e  fox.
+"#
+    );
+}
+
+#[test]
+fn uppercase() {
+    let dump = HexDump::new(b"\xab\xcd\xef").offset(0xabcdef).uppercase(true);
+    assert_eq!(dump.to_string(), "00ABCDEF: ABCD EF                                  ...\n");
+}
+
+#[test]
+fn empty() {
+    assert_eq!(HexDump::new(b"").to_string(), "");
+}
+
+#[test]
+fn large_offset() {
+    let dump = HexDump::new(b"ab").width(1).offset(0xffffffff);
+    assert_eq!(dump.to_string(), "ffffffff: 61  a\n100000000: 62  b\n");
+}
+
+#[test]
+#[should_panic(expected = "width must be positive")]
+fn zero_width() {
+    let _ = HexDump::new(b"").width(0);
+}
+
+fn error(position: usize, kind: DecodeKind) -> DecodeError {
+    DecodeError { position, kind }
+}
+
+#[test]
+fn parse_round_trip() {
+    for len in 0 .. INPUT.len() {
+        let input = &INPUT[.. len];
+        for &(width, group) in &[(16, 2), (10, 4), (16, 0), (7, 1), (1, 3)] {
+            let dump = HexDump::new(input).width(width).group(group).offset(0x42);
+            assert_eq!(parse(dump.to_string().as_bytes(), Format::Xxd).unwrap(), input);
+            let dump = dump.uppercase(true).to_string().replace('\n', "\r\n");
+            assert_eq!(parse(dump.as_bytes(), Format::Xxd).unwrap(), input);
+        }
+    }
+}
+
+// The inputs have been generated with xxd -a, od -x, and hexdump -C.
+#[test]
+fn parse_squeeze() {
+    let mut output = b"ab".to_vec();
+    output.extend_from_slice(&[0; 70]);
+    output.extend_from_slice(b"xyz");
+    let xxd = r#"00000000: 6162 0000 0000 0000 0000 0000 0000 0000  ab..............
+00000010: 0000 0000 0000 0000 0000 0000 0000 0000  ................
+*
+00000040: 0000 0000 0000 0000 7879 7a              ........xyz
+"#;
+    assert_eq!(parse(xxd.as_bytes(), Format::Xxd).unwrap(), output);
+    let od = r#"0000000 6261 0000 0000 0000 0000 0000 0000 0000
+0000020 0000 0000 0000 0000 0000 0000 0000 0000
+*
+0000100 0000 0000 0000 0000 7978 007a
+0000113
+"#;
+    assert_eq!(parse(od.as_bytes(), Format::Od).unwrap(), output);
+    let hexdump = r#"00000000  61 62 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |ab..............|
+00000010  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|
+*
+00000040  00 00 00 00 00 00 00 00  78 79 7a                 |........xyz|
+0000004b
+"#;
+    assert_eq!(parse(hexdump.as_bytes(), Format::Hexdump).unwrap(), output);
+    let hexdump = "00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  \
+                   |................|\n*\n00000030\n";
+    assert_eq!(parse(hexdump.as_bytes(), Format::Hexdump).unwrap(), [0; 48]);
+}
+
+#[test]
+fn parse_od() {
+    assert_eq!(parse(b"0000000 6261 0063\n0000003\n", Format::Od).unwrap(), b"abc");
+    assert_eq!(parse(b"0000000 6261 0063\n", Format::Od).unwrap(), b"ab\x63\x00");
+    assert_eq!(parse(b"", Format::Od).unwrap(), b"");
+    assert_eq!(parse(b"0000000\n", Format::Od).unwrap(), b"");
+}
+
+#[test]
+fn parse_error() {
+    let check = |input: &str, format, position, kind| {
+        assert_eq!(parse(input.as_bytes(), format).unwrap_err(), error(position, kind));
+    };
+    check("00000000: 4865 6g6c  He.l", Format::Xxd, 16, DecodeKind::Symbol);
+    check("00000000: 4865 6c6  He.", Format::Xxd, 17, DecodeKind::Length);
+    check("00000000 4865", Format::Xxd, 8, DecodeKind::Symbol);
+    check("00000000: 48\nxyz: 65\n", Format::Xxd, 13, DecodeKind::Symbol);
+    check("*\n00000010: 48\n", Format::Xxd, 0, DecodeKind::Symbol);
+    check("00000000: 4865\n*\n00000003: 6c\n", Format::Xxd, 15, DecodeKind::Length);
+    check("00000000: 4865\n*\n", Format::Xxd, 15, DecodeKind::Length);
+    check("00000000: 4865\n*\nffffffffffff:\n", Format::Xxd, 15, DecodeKind::Length);
+    assert_eq!(parse(b"00000000: 4865\n*\n000f0000:\n", Format::Xxd).unwrap().len(), 0xf0000);
+    check("00000000  48 65 6x  |Hel|", Format::Hexdump, 17, DecodeKind::Symbol);
+    check("0000000 6548 6c6\n", Format::Od, 16, DecodeKind::Length);
+    check("0000000 6548 6c6c1\n", Format::Od, 17, DecodeKind::Length);
+    check("0000000 6548\n0000008 6c6c\n", Format::Od, 19, DecodeKind::Symbol);
+    check("0000000 6548 6z6c\n", Format::Od, 14, DecodeKind::Symbol);
+}
+
+#[test]
+fn parse_limit() {
+    let check = |input: &str, format, max_len, position| {
+        let actual = parse_with_limit(input.as_bytes(), format, max_len).unwrap_err();
+        assert_eq!(actual, error(position, DecodeKind::Length));
+    };
+    let squeeze = b"00000000: 4865\n*\n00200000:\n";
+    assert_eq!(parse_with_limit(squeeze, Format::Xxd, 0x200000).unwrap().len(), 0x200000);
+    check("00000000: 4865\n*\n00200000:\n", Format::Xxd, 0x1fffff, 15);
+    check("00000000: 4865\n*\n00200000: 6c\n", Format::Xxd, 0x200000, 26);
+    check("00000000: 4865 6c\n", Format::Xxd, 2, 9);
+    check("0000000 6548 6c6c\n", Format::Od, 3, 7);
+    check("00000000  48 65 6c  |Hel|\n", Format::Hexdump, 0, 8);
+    assert_eq!(parse_with_limit(b"00000000: 4865 6c\n00000002:\n", Format::Xxd, 3).unwrap(), b"He");
+    assert_eq!(parse_with_limit(b"", Format::Xxd, 0).unwrap(), b"");
+}
@@ -1,5 +1,7 @@
 //! Library tests
 
+use core::num::NonZeroUsize;
+
 use data_encoding::DecodeKind::*;
 use data_encoding::{DecodeError, Encoding, Specification};
 
@@ -389,6 +391,11 @@ fn specification() {
     assert_eq!(spec.encoding().unwrap(), previous_encoding);
     spec.wrap.width = 256;
     assert_eq!(errmsg(spec.encoding()), "invalid wrap width or separator length");
+    spec.wrap.width = 4;
+    let base = spec.encoding().unwrap();
+    let errmsg = |prefix: &str| base.with_wrap_prefix(prefix).unwrap_err().to_string();
+    assert_eq!(errmsg(&" ".repeat(256)), "invalid wrap width or separator length");
+    assert_eq!(errmsg("a"), "'a' has conflicting definitions");
 }
 
 #[test]
@@ -601,6 +608,28 @@ fn encode_pad_wrap() {
     assert_eq!(b.encode(b"hello"), "aGVs:bG8=:");
 }
 
+#[test]
+fn encode_wrap_prefix() {
+    let mut spec = data_encoding::BASE64.specification();
+    spec.wrap.width = 4;
+    spec.wrap.separator.push('\n');
+    let b = spec.encoding().unwrap().with_wrap_prefix("> ").unwrap();
+    assert_eq!(b.encode(b""), "");
+    assert_eq!(b.encode(b"h"), "> aA==\n");
+    assert_eq!(b.encode(b"hel"), "> aGVs\n");
+    assert_eq!(b.encode(b"hell"), "> aGVs\n> bA==\n");
+    assert_eq!(b.encode(b"hello"), "> aGVs\n> bG8=\n");
+    assert_eq!(b.encode_len(5), 14);
+    assert_eq!(b.decode(b"> aGVs\n> bG8=\n").unwrap(), b"hello");
+    assert_eq!(b.decode(b"aGVsbG8=").unwrap(), b"hello");
+    assert_eq!(b.specification().ignore, "\n >");
+    assert_eq!(b.specification().encoding().unwrap().with_wrap_prefix("> ").unwrap(), b);
+    assert_eq!(b.with_wrap_prefix("").unwrap().encode(b"hello"), "aGVs\nbG8=\n");
+    let mut spec = data_encoding::BASE64.specification();
+    spec.wrap.width = 0;
+    assert_eq!(spec.encoding().unwrap().with_wrap_prefix("> ").unwrap().encode(b"hi"), "aGk=");
+}
+
 #[test]
 fn decode_pad_wrap() {
     let mut spec = data_encoding::BASE64.specification();
@@ -701,3 +730,584 @@ fn encoder() {
     test(&[b"foob", b"a"], "Zm9vYmE=");
     test(&[b"foob", b"ar"], "Zm9vYmFy");
 }
+
+#[test]
+fn decoder() {
+    #[track_caller]
+    fn test(base: &Encoding, inputs: &[&[u8]], expected: Result<&[u8], DecodeError>) {
+        let mut output = Vec::new();
+        let mut decoder = base.new_decoder(&mut output);
+        let mut result = Ok(());
+        for input in inputs {
+            result = decoder.append(input);
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = decoder.finalize();
+        }
+        match expected {
+            Ok(expected) => {
+                assert_eq!(result, Ok(()));
+                assert_eq!(output, expected);
+            }
+            Err(expected) => assert_eq!(result, Err(expected)),
+        }
+    }
+    #[track_caller]
+    fn test_splits(base: &Encoding, input: &[u8]) {
+        let expected = base.decode(input);
+        for i in 0 ..= input.len() {
+            for j in i ..= input.len() {
+                let inputs = [&input[.. i], &input[i .. j], &input[j ..]];
+                test(base, &inputs, expected.as_deref().map_err(|&e| e));
+            }
+        }
+    }
+    let b = &data_encoding::BASE64;
+    test(b, &[], Ok(b""));
+    test(b, &[b"", b""], Ok(b""));
+    test(b, &[b"Zm", b"9v"], Ok(b"foo"));
+    test(b, &[b"Zm9", b"vYg", b"=="], Ok(b"foob"));
+    test(b, &[b"Zg==Zg", b"=="], Ok(b"ff"));
+    test(b, &[b"Zm9", b"v", b"Y"], Err(DecodeError { position: 4, kind: Length }));
+    test(b, &[b"Zm9v", b"Y.=="], Err(DecodeError { position: 5, kind: Symbol }));
+    test(b, &[b"Zm9vY", b"h=="], Err(DecodeError { position: 5, kind: Trailing }));
+    test_splits(b, b"Zm9vYmE=Zg==");
+    test_splits(b, b"Zm9vYmE=Z===");
+    test_splits(b, b"Zm9vYm.=");
+    test_splits(&data_encoding::BASE64_NOPAD, b"Zm9vYmE");
+    test_splits(&data_encoding::BASE64_NOPAD, b"Zm9vYmF");
+    test_splits(&data_encoding::BASE64_NOPAD, b"Zm9vY");
+    test_splits(&data_encoding::HEXLOWER, b"666f6F");
+    test_splits(&data_encoding::BASE32, b"MZXW6YQ=MY======");
+    let b = &data_encoding::BASE64_MIME;
+    test_splits(b, b"Zm9v\r\nYmE=\r\n");
+    test_splits(b, b"Zm\r\n9v\r\r\nYg=\n=\r\nZg==");
+    test_splits(b, b"Zm\r\n9v\r\nYg\r\n");
+    test_splits(b, b"Zm\r\n9v\r\nY\r\n");
+    test_splits(b, b"Zm\r\n9v\r\nYh==\r\n");
+    test_splits(b, b"\n\n\nZm\r\n9v\r\nY!==\r\n");
+}
+
+// Reads at most `size` bytes at a time.
+struct Chunks<'a> {
+    data: &'a [u8],
+    size: usize,
+}
+
+impl std::io::Read for Chunks<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = std::cmp::min(std::cmp::min(self.size, buf.len()), self.data.len());
+        buf[.. len].copy_from_slice(&self.data[.. len]);
+        self.data = &self.data[len ..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn encode_reader() {
+    use std::io::Read;
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        let expected = base.encode(input);
+        for size in 1 ..= input.len() + 1 {
+            for buf_len in 1 .. 10 {
+                let mut reader = base.new_encode_reader(Chunks { data: input, size });
+                let mut output = Vec::new();
+                let mut buf = vec![0; buf_len];
+                loop {
+                    let len = reader.read(&mut buf).unwrap();
+                    if len == 0 {
+                        break;
+                    }
+                    output.extend_from_slice(&buf[.. len]);
+                }
+                assert_eq!(output, expected.as_bytes());
+            }
+        }
+    }
+    test(&data_encoding::BASE64, b"");
+    test(&data_encoding::BASE64, b"hello world");
+    test(&data_encoding::BASE64_NOPAD, b"hello world");
+    test(&data_encoding::BASE32, b"hello world");
+    test(&data_encoding::HEXUPPER, b"hello world");
+    let mut spec = data_encoding::BASE64.specification();
+    spec.wrap.width = 4;
+    spec.wrap.separator.push_str("\r\n");
+    test(&spec.encoding().unwrap(), b"hello world");
+    test(&spec.encoding().unwrap().with_wrap_prefix("  ").unwrap(), b"hello world");
+    test(&data_encoding::BASE64_MIME, &[0u8; 100]);
+}
+
+#[test]
+fn decode_reader() {
+    use std::io::Read;
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        let expected = base.decode(input);
+        for size in 1 ..= input.len() + 1 {
+            for buf_len in 1 .. 10 {
+                let mut reader = base.new_decode_reader(Chunks { data: input, size });
+                let mut output = Vec::new();
+                let mut buf = vec![0; buf_len];
+                let result = loop {
+                    match reader.read(&mut buf) {
+                        Ok(0) => break Ok(output),
+                        Ok(len) => output.extend_from_slice(&buf[.. len]),
+                        Err(error) => {
+                            assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+                            let error = error.into_inner().unwrap();
+                            break Err(*error.downcast::<DecodeError>().unwrap());
+                        }
+                    }
+                };
+                assert_eq!(result, expected);
+            }
+        }
+    }
+    test(&data_encoding::BASE64, b"");
+    test(&data_encoding::BASE64, b"aGVsbG8gd29ybGQ=");
+    test(&data_encoding::BASE64, b"aGVsbA==byB3b3JsZA==");
+    test(&data_encoding::BASE64, b"aGVsbG8gd29ybG!=");
+    test(&data_encoding::BASE64, b"aGVsbG8gd29ybGQ");
+    test(&data_encoding::BASE64_NOPAD, b"aGVsbG8gd29ybGQ");
+    test(&data_encoding::BASE64_NOPAD, b"aGVsbG8gd29ybGR");
+    test(&data_encoding::BASE32, b"NBSWY3DPEB3W64TMMQ======");
+    test(&data_encoding::HEXUPPER, b"68656C6C6F");
+    test(&data_encoding::HEXUPPER, b"68656C6C6");
+    test(&data_encoding::BASE64_MIME, b"aGVs\r\nbG8g\r\nd29y\r\nbGQ=\r\n");
+    test(&data_encoding::BASE64_MIME, b"aGVs\r\nbG8g\r\nd29y\r\nbGQ\r\n");
+    test(&data_encoding::BASE64_MIME, b"aG\r\nVsbG8gd2\r\n9ybGQ=\r\n");
+}
+
+#[test]
+fn encode_writer() {
+    use std::io::Write;
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        let expected = base.encode(input);
+        for size in 1 ..= input.len() + 1 {
+            let mut writer = base.new_encode_writer(Vec::new());
+            for chunk in input.chunks(size) {
+                writer.write_all(chunk).unwrap();
+            }
+            assert_eq!(writer.finish().unwrap(), expected.as_bytes());
+            let mut output = Vec::new();
+            let mut writer = base.new_encode_writer(&mut output);
+            for chunk in input.chunks(size) {
+                writer.write_all(chunk).unwrap();
+            }
+            drop(writer);
+            assert_eq!(output, expected.as_bytes());
+        }
+    }
+    test(&data_encoding::BASE64, b"");
+    test(&data_encoding::BASE64, b"hello world");
+    test(&data_encoding::BASE64_NOPAD, b"hello world");
+    test(&data_encoding::BASE32, b"hello world");
+    test(&data_encoding::HEXUPPER, b"hello world");
+    test(&data_encoding::BASE64_MIME, &[0u8; 100]);
+    let mut spec = data_encoding::BASE64.specification();
+    spec.wrap.width = 4;
+    spec.wrap.separator.push_str("\r\n");
+    test(&spec.encoding().unwrap(), b"hello world");
+    test(&spec.encoding().unwrap().with_wrap_prefix("  ").unwrap(), b"hello world");
+}
+
+#[test]
+fn decode_writer() {
+    use std::io::Write;
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        let expected = base.decode(input);
+        for size in 1 ..= input.len() + 1 {
+            let mut writer = base.new_decode_writer(Vec::new());
+            let result = input
+                .chunks(size)
+                .try_for_each(|chunk| writer.write_all(chunk))
+                .and_then(|()| writer.finish());
+            let result = result.map_err(|error| {
+                assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+                *error.into_inner().unwrap().downcast::<DecodeError>().unwrap()
+            });
+            assert_eq!(result, expected);
+        }
+    }
+    test(&data_encoding::BASE64, b"");
+    test(&data_encoding::BASE64, b"aGVsbG8gd29ybGQ=");
+    test(&data_encoding::BASE64, b"aGVsbA==byB3b3JsZA==");
+    test(&data_encoding::BASE64, b"aGVsbG8gd29ybG!=");
+    test(&data_encoding::BASE64, b"aGVsbG8gd29ybGQ");
+    test(&data_encoding::BASE64_NOPAD, b"aGVsbG8gd29ybGQ");
+    test(&data_encoding::BASE64_NOPAD, b"aGVsbG8gd29ybGR");
+    test(&data_encoding::HEXUPPER, b"68656C6C6F");
+    test(&data_encoding::HEXUPPER, b"68656C6C6");
+    test(&data_encoding::BASE64_MIME, b"aGVs\r\nbG8g\r\nd29y\r\nbGQ=\r\n");
+    test(&data_encoding::BASE64_MIME, b"aG\r\nVsbG8gd2\r\n9ybGQ\r\n");
+}
+
+#[test]
+fn simd() {
+    // Naive implementation of most significant bit first encodings.
+    fn encode(base: &Encoding, input: &[u8]) -> Vec<u8> {
+        let spec = base.specification();
+        let symbols = spec.symbols.into_bytes();
+        let bit = base.bit_width();
+        let mut bits = Vec::new();
+        for x in input {
+            bits.extend((0 .. 8).rev().map(|i| x >> i & 1));
+        }
+        while bits.len() % bit != 0 {
+            bits.push(0);
+        }
+        let mut output = Vec::new();
+        for chunk in bits.chunks(bit) {
+            let x = chunk.iter().fold(0, |x, b| x << 1 | *b as usize);
+            output.push(symbols[x]);
+        }
+        if let Some(padding) = spec.padding {
+            while output.len() % 4 != 0 {
+                output.push(padding as u8);
+            }
+        }
+        output
+    }
+    let mut hex_custom = Specification::new();
+    hex_custom.symbols.push_str("qwertyuiopasdfgh");
+    let mut base64_custom = Specification::new();
+    base64_custom
+        .symbols
+        .push_str("zyxwvutsrqponmlkjihgfedcba9876543210ZYXWVUTSRQPONMLKJIHGFEDCBA+/");
+    base64_custom.padding = Some('=');
+    let bases = [
+        data_encoding::BASE64,
+        data_encoding::BASE64URL,
+        data_encoding::HEXLOWER,
+        data_encoding::HEXUPPER,
+        hex_custom.encoding().unwrap(),
+        base64_custom.encoding().unwrap(),
+    ];
+    let mut state = 0x2545f491u32;
+    let data: Vec<u8> = (0 .. 130)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+    for base in &bases {
+        for len in 0 .. data.len() {
+            let input = &data[.. len];
+            let encoded = base.encode(input);
+            assert_eq!(encoded.as_bytes(), &encode(base, input)[..]);
+            assert_eq!(base.decode(encoded.as_bytes()).unwrap(), input);
+            let size = base.decode_len(encoded.len()).unwrap();
+            let padding = encoded.bytes().rev().take_while(|&x| x == b'=').count();
+            for position in 0 .. encoded.len() - padding {
+                for &invalid in &[b'!', 0xff] {
+                    let mut encoded = encoded.clone().into_bytes();
+                    encoded[position] = invalid;
+                    let mut output = vec![0; size];
+                    let error = base.decode_mut(&encoded, &mut output).unwrap_err();
+                    assert_eq!(error.error, DecodeError { position, kind: Symbol });
+                    assert_eq!(output[.. error.written], input[.. error.written]);
+                }
+            }
+        }
+    }
+}
+
+/// Calls `test` on valid and corrupted encodings of `lens` bytes with each of `bases`
+///
+/// All positions are corrupted for lengths below `full_len` and only some of them otherwise. When
+/// `repeat` is not zero, corruptions also include runs of `repeat` identical characters.
+fn for_each_decode_case(
+    bases: &[Encoding], lens: &[usize], full_len: usize, repeat: usize,
+    mut test: impl FnMut(&Encoding, &[u8]),
+) {
+    for base in bases {
+        for &len in lens {
+            let data: Vec<u8> = (0 .. len).map(|i| (i * 37 + 11) as u8).collect();
+            let encoded = base.encode(&data);
+            test(base, encoded.as_bytes());
+            let positions: Vec<usize> = if len < full_len {
+                (0 .. encoded.len()).collect()
+            } else {
+                (0 .. encoded.len()).step_by(61).chain(encoded.len() - 9 .. encoded.len()).collect()
+            };
+            for &position in &positions {
+                for &x in b"aZ=!\n " {
+                    let mut input = encoded.clone().into_bytes();
+                    input[position] = x;
+                    test(base, &input);
+                    input.insert(position, x);
+                    test(base, &input);
+                    let _ = input.remove(position + 1);
+                    test(base, &input[.. position]);
+                    if repeat == 0 {
+                        continue;
+                    }
+                    let mut input = encoded.clone().into_bytes();
+                    drop(input.splice(position .. position, core::iter::repeat(x).take(repeat)));
+                    test(base, &input);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_ct() {
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        assert_eq!(base.decode_ct(input), base.decode(input));
+    }
+    let mut bases = vec![
+        data_encoding::HEXLOWER_PERMISSIVE,
+        data_encoding::BASE32,
+        data_encoding::BASE32_DNSCURVE,
+        data_encoding::BASE64,
+        data_encoding::BASE64_NOPAD,
+        data_encoding::BASE64_MIME,
+    ];
+    let mut spec = data_encoding::BASE64.specification();
+    spec.check_trailing_bits = false;
+    spec.ignore.push_str(" \n");
+    bases.push(spec.encoding().unwrap());
+    let mut spec = data_encoding::BASE32_NOPAD.specification();
+    spec.bit_order = data_encoding::BitOrder::LeastSignificantFirst;
+    spec.ignore.push(' ');
+    bases.push(spec.encoding().unwrap());
+    for_each_decode_case(&bases, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], usize::MAX, 0, test);
+    test(&data_encoding::BASE64, b"SGVsbA==byB3b3JsZA==");
+    test(&data_encoding::BASE64, b"SGVsbB==byB3b3JsZA==");
+    test(&data_encoding::BASE64, b"SGVsbA==byB3b3JsZB==");
+}
+
+#[test]
+fn decode_in_place() {
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        let mut output = vec![0; base.decode_len(input.len()).unwrap_or(0)];
+        let expected = match base.decode_len(input.len()) {
+            Err(error) => Err(data_encoding::DecodePartial { read: 0, written: 0, error }),
+            Ok(_) => base.decode_mut(input, &mut output),
+        };
+        let mut buffer = input.to_vec();
+        match (base.decode_in_place(&mut buffer), expected) {
+            (Ok(actual), Ok(len)) => assert_eq!(actual, &output[.. len]),
+            (Err(actual), Err(expected)) => {
+                // The partial progress may differ but must be consistent.
+                assert_eq!(actual.error, expected.error);
+                assert!(actual.read <= actual.error.position);
+                let mut prefix = input[.. actual.read].to_vec();
+                assert_eq!(base.decode_in_place(&mut prefix).unwrap(), &buffer[.. actual.written]);
+            }
+            (actual, expected) => panic!("{:?} != {:?}", actual, expected),
+        }
+    }
+    let mut bases = vec![
+        data_encoding::HEXLOWER_PERMISSIVE,
+        data_encoding::BASE32,
+        data_encoding::BASE64,
+        data_encoding::BASE64_NOPAD,
+        data_encoding::BASE64_MIME,
+    ];
+    let mut spec = data_encoding::BASE64.specification();
+    spec.ignore.push_str(" \n");
+    bases.push(spec.encoding().unwrap());
+    let mut spec = data_encoding::BASE32_NOPAD.specification();
+    spec.bit_order = data_encoding::BitOrder::LeastSignificantFirst;
+    spec.ignore.push(' ');
+    bases.push(spec.encoding().unwrap());
+    // Ignored characters across chunks.
+    for_each_decode_case(&bases, &[0, 1, 2, 5, 11, 380, 389, 1000], 100, 700, test);
+    test(&data_encoding::BASE64, b"SGVsbA==byB3b3JsZA==");
+    test(&data_encoding::BASE64, b"SGVsbA==byB3b3JsZB==");
+}
+
+#[test]
+fn encode_in_place() {
+    let mut bases = vec![
+        data_encoding::HEXLOWER,
+        data_encoding::BASE32,
+        data_encoding::BASE32_DNSCURVE,
+        data_encoding::BASE64,
+        data_encoding::BASE64_NOPAD,
+        data_encoding::BASE64_MIME,
+    ];
+    let mut spec = data_encoding::BASE32.specification();
+    spec.wrap.width = 8;
+    spec.wrap.separator.push_str(" |\n");
+    bases.push(spec.encoding().unwrap());
+    bases.push(spec.encoding().unwrap().with_wrap_prefix("\t").unwrap());
+    let mut spec = Specification::new();
+    spec.symbols.push_str("01");
+    spec.wrap.width = 248;
+    spec.wrap.separator.push('\n');
+    bases.push(spec.encoding().unwrap());
+    for base in &bases {
+        for &len in &[0, 1, 2, 3, 4, 5, 56, 57, 58, 1023, 1024, 1025, 3000, 5000] {
+            let data: Vec<u8> = (0 .. len).map(|i| (i * 37 + 11) as u8).collect();
+            let expected = base.encode(&data);
+            let mut buffer = data.clone();
+            buffer.resize(expected.len() + 3, 0xff);
+            assert_eq!(base.encode_in_place(&mut buffer, len), expected);
+            assert_eq!(&buffer[expected.len() ..], [0xff; 3]);
+            let mut buffer = data.clone();
+            assert_eq!(base.encode_in_place_vec(&mut buffer), expected);
+            assert_eq!(buffer, expected.as_bytes());
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn encode_in_place_too_small() {
+    let mut buffer = *b"Hello world\0\0\0\0";
+    let _ = data_encoding::BASE64.encode_in_place(&mut buffer, 11);
+}
+
+#[test]
+fn decode_diagnose() {
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        let (output, errors) = base.decode_diagnose(input);
+        match base.decode(input) {
+            Ok(expected) => {
+                assert_eq!(output, expected);
+                assert!(errors.is_empty());
+            }
+            Err(error) => {
+                assert!(errors.contains(&error), "{:?} not in {:?}", error, errors);
+                for pair in errors.windows(2) {
+                    assert!(pair[0].position < pair[1].position);
+                }
+            }
+        }
+        for limit in 1 .. 3 {
+            let (limited, limited_errors) =
+                base.decode_diagnose_limit(input, NonZeroUsize::new(limit).unwrap());
+            assert_eq!(limited_errors, errors[.. errors.len().min(limit)]);
+            assert!(output.starts_with(&limited));
+        }
+    }
+    let mut bases = vec![
+        data_encoding::HEXLOWER_PERMISSIVE,
+        data_encoding::BASE32,
+        data_encoding::BASE64,
+        data_encoding::BASE64_NOPAD,
+        data_encoding::BASE64_MIME,
+    ];
+    let mut spec = data_encoding::BASE32_NOPAD.specification();
+    spec.ignore.push(' ');
+    bases.push(spec.encoding().unwrap());
+    for_each_decode_case(&bases, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], usize::MAX, 0, test);
+    let base = &data_encoding::BASE64;
+    let (output, errors) = base.decode_diagnose(b"SGVs!G8gd2*ybGQ=SGVsb===SGVsbA");
+    assert_eq!(output, b"HelldHelHel");
+    assert_eq!(
+        errors,
+        [
+            DecodeError { position: 4, kind: Symbol },
+            DecodeError { position: 10, kind: Symbol },
+            DecodeError { position: 21, kind: Padding },
+            DecodeError { position: 28, kind: Length },
+        ]
+    );
+    let base = &data_encoding::BASE64_MIME;
+    let (output, errors) = base.decode_diagnose(b"SGVs\r\nbG8*\r\nd29yb===\r\n");
+    assert_eq!(output, b"Helwor");
+    assert_eq!(
+        errors,
+        [DecodeError { position: 9, kind: Symbol }, DecodeError { position: 17, kind: Padding },]
+    );
+    let one = NonZeroUsize::new(1).unwrap();
+    let (output, errors) = base.decode_diagnose_limit(b"SGVs\r\nbG8*\r\nd29yb===\r\n", one);
+    assert_eq!(output, b"Hel");
+    assert_eq!(errors, [DecodeError { position: 9, kind: Symbol }]);
+    let input = "!".repeat(1 << 20);
+    let (output, errors) = data_encoding::BASE64.decode_diagnose(input.as_bytes());
+    assert!(output.is_empty());
+    assert_eq!(errors.len(), 1 << 18);
+    let (_, errors) = data_encoding::BASE64.decode_diagnose_limit(input.as_bytes(), one);
+    assert_eq!(errors, [DecodeError { position: 0, kind: Symbol }]);
+}
+
+#[test]
+fn decoded_len_exact() {
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        let actual = base.decoded_len_exact(input);
+        match base.decode(input) {
+            Ok(output) => assert_eq!(actual, Ok(output.len())),
+            Err(error) => match error.kind {
+                Length | Padding => assert_eq!(actual, Err(error)),
+                Symbol | Trailing => (),
+            },
+        }
+    }
+    let mut bases = vec![
+        data_encoding::HEXLOWER_PERMISSIVE,
+        data_encoding::BASE32,
+        data_encoding::BASE64,
+        data_encoding::BASE64_NOPAD,
+        data_encoding::BASE64_MIME,
+    ];
+    let mut spec = data_encoding::BASE64.specification();
+    spec.ignore.push_str(" \n");
+    bases.push(spec.encoding().unwrap());
+    let mut spec = data_encoding::BASE32_NOPAD.specification();
+    spec.ignore.push(' ');
+    bases.push(spec.encoding().unwrap());
+    for_each_decode_case(&bases, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], usize::MAX, 0, test);
+    let base = &data_encoding::BASE64;
+    assert_eq!(base.decoded_len_exact(b"SGVsbA==byB3b3JsZA=="), Ok(11));
+    assert_eq!(
+        base.decoded_len_exact(b"SGVsb===").unwrap_err(),
+        DecodeError { position: 5, kind: Padding }
+    );
+    let base = &data_encoding::BASE64_MIME;
+    assert_eq!(base.decoded_len_exact(b"SGVs\r\nbA==\r\n"), Ok(4));
+    assert_eq!(
+        base.decoded_len_exact(b"SGVs\r\nbA=\r\n").unwrap_err(),
+        DecodeError { position: 6, kind: Length }
+    );
+}
+
+#[test]
+fn validate() {
+    #[track_caller]
+    fn test(base: &Encoding, input: &[u8]) {
+        assert_eq!(base.validate(input), base.decode(input).map(|_| ()));
+    }
+    let mut bases = vec![
+        data_encoding::HEXLOWER_PERMISSIVE,
+        data_encoding::BASE32,
+        data_encoding::BASE32_DNSCURVE,
+        data_encoding::BASE64,
+        data_encoding::BASE64_NOPAD,
+        data_encoding::BASE64_MIME,
+    ];
+    let mut spec = data_encoding::BASE64.specification();
+    spec.check_trailing_bits = false;
+    spec.ignore.push_str(" \n");
+    bases.push(spec.encoding().unwrap());
+    let mut spec = data_encoding::BASE32_NOPAD.specification();
+    spec.bit_order = data_encoding::BitOrder::LeastSignificantFirst;
+    spec.ignore.push(' ');
+    bases.push(spec.encoding().unwrap());
+    for_each_decode_case(
+        &bases,
+        &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 100, 101],
+        usize::MAX,
+        0,
+        test,
+    );
+    test(&data_encoding::BASE64, b"SGVsbA==byB3b3JsZA==");
+    test(&data_encoding::BASE64, b"SGVsbB==byB3b3JsZA==");
+    test(&data_encoding::BASE64, b"SGVsbA==byB3b3JsZB==");
+}
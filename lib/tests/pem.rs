@@ -0,0 +1,155 @@
+//! PEM tests
+
+#![cfg(feature = "alloc")]
+
+use data_encoding::pem::{decode, decode_all, encode, encode_all, Error, ErrorKind, Mode, Pem};
+use data_encoding::DecodeKind;
+
+fn pem(label: &str, headers: &[(&str, &str)], contents: &[u8]) -> Pem {
+    let headers = headers.iter().map(|&(n, v)| (n.to_string(), v.to_string())).collect();
+    Pem { label: label.to_string(), headers, contents: contents.to_vec() }
+}
+
+#[track_caller]
+fn error(input: &str, mode: Mode, line: usize, column: usize, kind: ErrorKind) {
+    assert_eq!(decode(input.as_bytes(), mode), Err(Error { line, column, kind }));
+}
+
+#[test]
+fn round_trip() {
+    for &len in &[0, 1, 2, 3, 47, 48, 49, 96, 100, 1000] {
+        let contents: Vec<u8> = (0 .. len).map(|x| (x * 37 + 11) as u8).collect();
+        let expected = pem("TEST DATA", &[], &contents);
+        let output = encode(&expected).unwrap();
+        assert!(output.lines().all(|x| x.len() <= 64 || x.starts_with("-----")));
+        assert_eq!(decode(output.as_bytes(), Mode::Strict).unwrap(), expected);
+        assert_eq!(decode(output.as_bytes(), Mode::Lax).unwrap(), expected);
+        let crlf = output.replace('\n', "\r\n");
+        assert_eq!(decode(crlf.as_bytes(), Mode::Strict).unwrap(), expected);
+    }
+    for &len in &[0, 3] {
+        let expected = pem("A:B", &[], &b"abc"[.. len]);
+        let output = encode(&expected).unwrap();
+        assert_eq!(decode(output.as_bytes(), Mode::Strict).unwrap(), expected);
+        assert_eq!(decode(output.as_bytes(), Mode::Lax).unwrap(), expected);
+    }
+}
+
+#[test]
+fn multiple_blocks() {
+    let pems = vec![
+        pem("CERTIFICATE", &[], b"first"),
+        pem("X509 CRL", &[], b""),
+        pem("PRIVATE KEY", &[("Name", "value")], b"third"),
+    ];
+    let output = encode_all(&pems).unwrap();
+    assert_eq!(decode_all(output.as_bytes(), Mode::Strict).unwrap(), pems);
+    let output = output.replace("-----END CERTIFICATE-----\n", "-----END CERTIFICATE-----\ntext\n");
+    let input = format!("Subject: test\n\n{}Trailing text\n", output);
+    assert_eq!(decode_all(input.as_bytes(), Mode::Strict).unwrap(), pems);
+    assert_eq!(decode(input.as_bytes(), Mode::Strict).unwrap(), pems[0]);
+    assert_eq!(decode_all(b"no block\n", Mode::Strict).unwrap(), vec![]);
+    error("no block\n", Mode::Strict, 2, 1, ErrorKind::MissingBegin);
+    error("no block", Mode::Lax, 1, 9, ErrorKind::MissingBegin);
+}
+
+#[test]
+fn headers() {
+    let input = concat!(
+        "-----BEGIN PRIVACY-ENHANCED MESSAGE-----\n",
+        "Proc-Type: 4,ENCRYPTED\n",
+        "Content-Domain: RFC822\n",
+        "DEK-Info: DES-CBC,\n",
+        " BFF968AA74691AC1\n",
+        "\n",
+        "aGVsbG8=\n",
+        "-----END PRIVACY-ENHANCED MESSAGE-----\n",
+    );
+    let expected = pem(
+        "PRIVACY-ENHANCED MESSAGE",
+        &[
+            ("Proc-Type", "4,ENCRYPTED"),
+            ("Content-Domain", "RFC822"),
+            ("DEK-Info", "DES-CBC, BFF968AA74691AC1"),
+        ],
+        b"hello",
+    );
+    assert_eq!(decode(input.as_bytes(), Mode::Strict).unwrap(), expected);
+    assert_eq!(decode(input.as_bytes(), Mode::Lax).unwrap(), expected);
+    let input = input.replace("\n\naGVs", "\n  \naGVs");
+    assert_eq!(decode(input.as_bytes(), Mode::Lax).unwrap(), expected);
+    // The line with whitespace is a folded value in strict mode.
+    error(&input, Mode::Strict, 7, 9, ErrorKind::Header);
+}
+
+#[test]
+fn lax() {
+    let input = concat!(
+        "  -----BEGIN TEST-----  \r\n",
+        "\taGVs bG8g\r\n",
+        "\r\n",
+        "d29y\x0bbGQ=\x0c\r\n",
+        "-----END TEST-----\t",
+    );
+    let expected = pem("TEST", &[], b"hello world");
+    assert_eq!(decode(input.as_bytes(), Mode::Lax).unwrap(), expected);
+    assert_eq!(
+        decode(input.trim_start().as_bytes(), Mode::Strict).unwrap_err().kind,
+        ErrorKind::Boundary
+    );
+}
+
+#[test]
+fn decode_errors() {
+    let base64 = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+    let block = |body: &str| format!("-----BEGIN A-----\n{}-----END A-----\n", body);
+    error("-----BEGIN A", Mode::Strict, 1, 13, ErrorKind::Boundary);
+    error("-----BEGIN A----- x", Mode::Strict, 1, 18, ErrorKind::Boundary);
+    error("-----BEGIN A-----\n", Mode::Strict, 2, 1, ErrorKind::MissingEnd);
+    error("-----BEGIN A-----\nAAAA\n", Mode::Lax, 3, 1, ErrorKind::MissingEnd);
+    error("-----BEGIN A--B-----", Mode::Strict, 1, 14, ErrorKind::Label);
+    error("-----BEGIN A\tB-----", Mode::Strict, 1, 13, ErrorKind::Label);
+    error("-----BEGIN  A-----", Mode::Strict, 1, 12, ErrorKind::Label);
+    error("-----BEGIN A-----\n-----END B-----\n", Mode::Strict, 2, 10, ErrorKind::Label);
+    error(&block("Name: value\nother\n\n"), Mode::Strict, 3, 6, ErrorKind::Header);
+    error(&block(": value\n\n"), Mode::Strict, 2, 1, ErrorKind::Header);
+    error(&block("A B: value\n\n"), Mode::Strict, 2, 2, ErrorKind::Header);
+    error(&block("Name: value\n"), Mode::Strict, 3, 16, ErrorKind::Header);
+    error(
+        &block(&format!("{}\nAAAA\n", &base64[4 ..])),
+        Mode::Strict,
+        2,
+        61,
+        ErrorKind::LineLength,
+    );
+    error(&block(&format!("{}A\n", base64)), Mode::Strict, 2, 65, ErrorKind::LineLength);
+    error(&block(&format!("{}\n\n", base64)), Mode::Strict, 3, 1, ErrorKind::LineLength);
+    let input = block(&format!("{}\n{}AAAA\n", base64, base64));
+    error(&input, Mode::Strict, 3, 65, ErrorKind::LineLength);
+    assert_eq!(decode(input.as_bytes(), Mode::Lax).unwrap().contents, [0; 99]);
+    let decode_error = |input: &str, mode, line, column, kind| {
+        error(&block(input), mode, line, column, ErrorKind::Decode(kind));
+    };
+    decode_error(&format!("{}\nAA!A\n", base64), Mode::Strict, 3, 3, DecodeKind::Symbol);
+    decode_error("AA AAAAA\n", Mode::Strict, 2, 3, DecodeKind::Symbol);
+    decode_error("AA AAA\n", Mode::Lax, 2, 6, DecodeKind::Length);
+    decode_error(&format!("{}\nAAB=\n", base64), Mode::Strict, 3, 3, DecodeKind::Trailing);
+    decode_error("AA==AA==\n", Mode::Strict, 2, 3, DecodeKind::Padding);
+    decode_error("AA==\n AA==\n", Mode::Lax, 2, 3, DecodeKind::Padding);
+    let error = decode(block("AA!A\n").as_bytes(), Mode::Strict).unwrap_err();
+    assert_eq!(error.to_string(), "invalid symbol at line 2 column 3");
+}
+
+#[test]
+fn encode_errors() {
+    let check = |pem: &Pem, line, column, kind| {
+        assert_eq!(encode(pem), Err(Error { line, column, kind }));
+    };
+    check(&pem("A-", &[], b""), 1, 13, ErrorKind::Label);
+    check(&pem("\u{e9}", &[], b""), 1, 12, ErrorKind::Label);
+    check(&pem("A", &[("Name", "value"), ("", "value")], b""), 3, 1, ErrorKind::Header);
+    check(&pem("A", &[("Na:me", "value")], b""), 2, 3, ErrorKind::Header);
+    check(&pem("A", &[("Name", "val\nue")], b""), 2, 10, ErrorKind::Header);
+    let pems = [pem("A", &[], b"hello"), pem("B", &[], b""), pem("C D ", &[], b"")];
+    assert_eq!(encode_all(&pems), Err(Error { line: 6, column: 15, kind: ErrorKind::Label }));
+}
@@ -0,0 +1,117 @@
+//! Rayon tests
+
+#![cfg(feature = "rayon")]
+
+use data_encoding::{
+    DecodePartial, Encoding, Specification, BASE32, BASE64, BASE64_MIME, BASE64_NOPAD, HEXLOWER,
+};
+
+fn encodings() -> Vec<Encoding> {
+    let mut spec = BASE64.specification();
+    spec.ignore.push_str(" \n");
+    let ignore = spec.encoding().unwrap();
+    let mut spec = Specification::new();
+    spec.symbols.push_str("01234567");
+    spec.padding = Some('=');
+    spec.ignore.push(' ');
+    spec.wrap.width = 96;
+    spec.wrap.separator.push(' ');
+    let octal = spec.encoding().unwrap();
+    vec![HEXLOWER, BASE32, BASE64, BASE64_NOPAD, BASE64_MIME, ignore, octal]
+}
+
+fn data(len: usize) -> Vec<u8> {
+    (0 .. len).map(|x| (x * 7 + x / 13) as u8).collect()
+}
+
+fn decode(encoding: &Encoding, input: &[u8]) -> Result<Vec<u8>, DecodePartial> {
+    let mut output = vec![0; encoding.decode_len(input.len()).unwrap()];
+    let len = encoding.decode_mut(input, &mut output)?;
+    output.truncate(len);
+    Ok(output)
+}
+
+fn par_decode(encoding: &Encoding, input: &[u8]) -> Result<Vec<u8>, DecodePartial> {
+    let mut output = vec![0; encoding.decode_len(input.len()).unwrap()];
+    let len = encoding.par_decode_mut(input, &mut output)?;
+    output.truncate(len);
+    Ok(output)
+}
+
+const LENS: &[usize] = &[0, 1, 5, 1000, 65535, 65536, 65537, 200_003, 1 << 20];
+
+#[test]
+fn encode() {
+    for encoding in &encodings() {
+        for &len in LENS {
+            let input = data(len);
+            let mut output = vec![0; encoding.encode_len(len)];
+            encoding.par_encode_mut(&input, &mut output);
+            assert_eq!(output, encoding.encode(&input).as_bytes());
+        }
+    }
+}
+
+#[test]
+fn decode_valid() {
+    for encoding in &encodings() {
+        for &len in LENS {
+            let input = encoding.encode(&data(len));
+            assert_eq!(par_decode(encoding, input.as_bytes()).unwrap(), data(len));
+        }
+    }
+}
+
+#[test]
+fn decode_concatenated() {
+    let mut spec = BASE64.specification();
+    spec.ignore.push('\n');
+    let base64 = spec.encoding().unwrap();
+    let input = b"aGVsbA==\nbw==\n".repeat(30000);
+    assert_eq!(par_decode(&base64, &input), decode(&base64, &input));
+    assert_eq!(par_decode(&BASE64, &input[.. 12 * 30000]), decode(&BASE64, &input[.. 12 * 30000]));
+}
+
+#[test]
+fn decode_invalid() {
+    for encoding in &encodings() {
+        let valid = encoding.encode(&data(150_000)).into_bytes();
+        let len = valid.len();
+        for &position in &[0, 3, 65535, 65536, 65540, 100_001, 131_071, len - 2, len - 1] {
+            for &symbols in &[&b"!"[..], b"=", b"==", b"A"] {
+                let mut input = valid.clone();
+                input[position] = symbols[0];
+                let expected = decode(encoding, &input);
+                assert_eq!(par_decode(encoding, &input), expected);
+                if len - 16 < position || expected.is_ok() {
+                    continue;
+                }
+                // The error with the smallest position is reported.
+                for (i, &x) in symbols.iter().enumerate() {
+                    input[len - 1 - i] = x;
+                }
+                assert_eq!(par_decode(encoding, &input), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_invalid_later_piece() {
+    // The error is in a later piece of a wrapped input (with line breaks across pieces).
+    let valid = BASE64_MIME.encode(&data(1 << 20)).into_bytes();
+    for &position in &[5 * 65536 + 3, 5 * 65536 + 76, 5 * 65536 + 77, valid.len() - 3] {
+        let mut input = valid.clone();
+        input[position] = b'*';
+        let len = BASE64_MIME.decode_len(input.len()).unwrap();
+        let mut expected_output = vec![0; len];
+        let expected = BASE64_MIME.decode_mut(&input, &mut expected_output).unwrap_err();
+        let mut output = vec![0; len];
+        let actual = BASE64_MIME.par_decode_mut(&input, &mut output).unwrap_err();
+        assert_eq!(actual.read, expected.read);
+        assert_eq!(actual.written, expected.written);
+        assert_eq!(actual.error.position, expected.error.position);
+        assert!(actual.error.position > 5 * 65536);
+        assert_eq!(output[.. actual.written], expected_output[.. expected.written]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Serde tests
+
+#![cfg(feature = "serde")]
+
+use data_encoding::serde::{Adapter, With};
+use data_encoding::{Encoding, Specification};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Fields {
+    #[serde(with = "data_encoding::serde::hexupper")]
+    vec: Vec<u8>,
+    #[serde(with = "data_encoding::serde::base32")]
+    array: [u8; 3],
+    #[serde(with = "data_encoding::serde::base64")]
+    option_vec: Option<Vec<u8>>,
+    #[serde(with = "data_encoding::serde::base64url_nopad")]
+    option_array: Option<[u8; 2]>,
+}
+
+fn check<T>(value: &T, json: &str)
+where
+    T: std::fmt::Debug + PartialEq + Serialize + for<'de> Deserialize<'de>,
+{
+    assert_eq!(serde_json::to_string(value).unwrap(), json);
+    assert_eq!(&serde_json::from_str::<T>(json).unwrap(), value);
+}
+
+fn error<T: std::fmt::Debug + for<'de> Deserialize<'de>>(json: &str) -> String {
+    serde_json::from_str::<T>(json).unwrap_err().to_string()
+}
+
+#[test]
+fn fields() {
+    let fields = Fields {
+        vec: vec![0x01, 0xab],
+        array: *b"foo",
+        option_vec: Some(b"fo".to_vec()),
+        option_array: Some([0xff, 0xfe]),
+    };
+    check(&fields, r#"{"vec":"01AB","array":"MZXW6===","option_vec":"Zm8=","option_array":"__4"}"#);
+    let fields = Fields { vec: vec![], array: [0; 3], option_vec: None, option_array: None };
+    check(&fields, r#"{"vec":"","array":"AAAAA===","option_vec":null,"option_array":null}"#);
+}
+
+#[test]
+fn errors() {
+    let json = |field: &str| {
+        let mut fields = [
+            ("vec", "\"\""),
+            ("array", "\"AAAAA===\""),
+            ("option_vec", "null"),
+            ("option_array", "null"),
+        ];
+        for x in &mut fields {
+            if field.starts_with(x.0) {
+                x.1 = &field[x.0.len() + 1 ..];
+            }
+        }
+        let fields: Vec<_> = fields.iter().map(|(k, v)| format!("\"{}\":{}", k, v)).collect();
+        format!("{{{}}}", fields.join(","))
+    };
+    assert!(error::<Fields>(&json("vec:\"01ab\"")).starts_with("invalid symbol at 2"));
+    assert!(error::<Fields>(&json("vec:\"01A\"")).starts_with("invalid length at 2"));
+    assert!(error::<Fields>(&json("array:\"MZXW6YQ=\""))
+        .starts_with("invalid length 4, expected an encoded string of 3 bytes"));
+    assert!(
+        error::<Fields>(&json("option_vec:\"Zm9=\"")).starts_with("non-zero trailing bits at 2")
+    );
+    assert!(error::<Fields>(&json("option_array:\"__4=\"")).starts_with("invalid symbol at 3"));
+    assert!(error::<Fields>(&json("vec:12")).starts_with("invalid type: integer `12`"));
+}
+
+#[test]
+fn references() {
+    #[derive(Serialize)]
+    struct Borrowed<'a> {
+        #[serde(serialize_with = "data_encoding::serde::hexlower::serialize")]
+        slice: &'a [u8],
+        #[serde(serialize_with = "data_encoding::serde::hexlower::serialize")]
+        option: Option<&'a [u8]>,
+    }
+    let borrowed = Borrowed { slice: &[0xc0, 0xff, 0xee], option: Some(&[0x42]) };
+    assert_eq!(serde_json::to_string(&borrowed).unwrap(), r#"{"slice":"c0ffee","option":"42"}"#);
+}
+
+#[test]
+fn with() {
+    struct Binary;
+    impl Adapter for Binary {
+        fn encoding() -> &'static Encoding {
+            static BINARY: std::sync::OnceLock<Encoding> = std::sync::OnceLock::new();
+            BINARY.get_or_init(|| {
+                let mut spec = Specification::new();
+                spec.symbols.push_str("01");
+                spec.ignore.push('_');
+                spec.encoding().unwrap()
+            })
+        }
+    }
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Bits {
+        #[serde(with = "With::<Binary>")]
+        byte: [u8; 1],
+        #[serde(with = "With::<Binary>", default)]
+        bytes: Option<Vec<u8>>,
+    }
+    check(
+        &Bits { byte: [0x5a], bytes: Some(vec![1, 2]) },
+        r#"{"byte":"01011010","bytes":"0000000100000010"}"#,
+    );
+    assert_eq!(
+        serde_json::from_str::<Bits>(r#"{"byte":"0101_1010"}"#).unwrap(),
+        Bits { byte: [0x5a], bytes: None }
+    );
+}
+
+#[test]
+fn modules() {
+    macro_rules! test {
+        ($($module:ident: $constant:ident,)*) => {
+            $({
+                use data_encoding::serde::$module::{deserialize, serialize};
+                let value = b"Hello world".to_vec();
+                let json = format!("{:?}", data_encoding::$constant.encode(&value));
+                let mut output = Vec::new();
+                serialize(&value, &mut serde_json::Serializer::new(&mut output)).unwrap();
+                assert_eq!(output, json.as_bytes());
+                let mut input = serde_json::Deserializer::from_str(&json);
+                assert_eq!(deserialize::<Vec<u8>, _>(&mut input).unwrap(), value);
+            })*
+        };
+    }
+    test! {
+        hexlower: HEXLOWER,
+        hexlower_permissive: HEXLOWER_PERMISSIVE,
+        hexupper: HEXUPPER,
+        hexupper_permissive: HEXUPPER_PERMISSIVE,
+        base32: BASE32,
+        base32_nopad: BASE32_NOPAD,
+        base32_nopad_nocase: BASE32_NOPAD_NOCASE,
+        base32_nopad_visual: BASE32_NOPAD_VISUAL,
+        base32hex: BASE32HEX,
+        base32hex_nopad: BASE32HEX_NOPAD,
+        base32_dnssec: BASE32_DNSSEC,
+        base32_dnscurve: BASE32_DNSCURVE,
+        base64: BASE64,
+        base64_nopad: BASE64_NOPAD,
+        base64_mime: BASE64_MIME,
+        base64_mime_permissive: BASE64_MIME_PERMISSIVE,
+        base64url: BASE64URL,
+        base64url_nopad: BASE64URL_NOPAD,
+    }
+}
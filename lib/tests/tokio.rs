@@ -0,0 +1,203 @@
+//! Tokio tests
+
+#![cfg(feature = "tokio")]
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use data_encoding::{
+    DecodeError, DecodeKind, Encoding, BASE32, BASE64, BASE64_MIME, BASE64_NOPAD, HEXUPPER,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+// Reader and writer processing at most `size` bytes at once and pending every other call.
+struct Chunks {
+    data: Vec<u8>,
+    size: usize,
+    pending: bool,
+}
+
+impl Chunks {
+    fn new(data: &[u8], size: usize) -> Self {
+        Chunks { data: data.to_vec(), size, pending: false }
+    }
+
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        Poll::Ready(())
+    }
+}
+
+impl AsyncRead for Chunks {
+    fn poll_read(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        let len = std::cmp::min(std::cmp::min(this.size, buf.remaining()), this.data.len());
+        buf.put_slice(&this.data[.. len]);
+        drop(this.data.drain(.. len));
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for Chunks {
+    fn poll_write(
+        self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        let len = std::cmp::min(this.size, buf.len());
+        this.data.extend_from_slice(&buf[.. len]);
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll(cx).map(Ok)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll(cx).map(Ok)
+    }
+}
+
+fn decode_error(error: io::Error) -> DecodeError {
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    *error.into_inner().unwrap().downcast::<DecodeError>().unwrap()
+}
+
+fn wrapped() -> Encoding {
+    let mut spec = BASE64.specification();
+    spec.wrap.width = 4;
+    spec.wrap.separator.push_str("\r\n");
+    spec.encoding().unwrap()
+}
+
+const ENCODE: &[(&Encoding, &[u8])] = &[
+    (&BASE64, b""),
+    (&BASE64, b"hello world"),
+    (&BASE64_NOPAD, b"hello world"),
+    (&BASE32, b"hello world"),
+    (&HEXUPPER, b"hello world"),
+    (&BASE64_MIME, &[0u8; 100]),
+];
+
+const DECODE: &[(&Encoding, &[u8])] = &[
+    (&BASE64, b""),
+    (&BASE64, b"aGVsbG8gd29ybGQ="),
+    (&BASE64, b"aGVsbA==byB3b3JsZA=="),
+    (&BASE64, b"aGVsbG8gd29ybG!="),
+    (&BASE64, b"aGVsbG8gd29ybGQ"),
+    (&BASE64_NOPAD, b"aGVsbG8gd29ybGR"),
+    (&BASE32, b"NBSWY3DPEB3W64TMMQ======"),
+    (&HEXUPPER, b"68656C6C6"),
+    (&BASE64_MIME, b"aGVs\r\nbG8g\r\nd29y\r\nbGQ=\r\n"),
+    (&BASE64_MIME, b"aG\r\nVsbG8gd2\r\n9ybGQ\r\n"),
+];
+
+#[tokio::test]
+async fn encode_reader() {
+    let wrapped = wrapped();
+    for &(base, input) in ENCODE.iter().chain(&[(&wrapped, &b"hello world"[..])]) {
+        let expected = base.encode(input);
+        for size in 1 ..= input.len() + 1 {
+            for buf_len in 1 .. 10 {
+                let mut reader = base.new_async_encode_reader(Chunks::new(input, size));
+                let mut output = Vec::new();
+                let mut buf = vec![0; buf_len];
+                loop {
+                    let len = reader.read(&mut buf).await.unwrap();
+                    if len == 0 {
+                        break;
+                    }
+                    output.extend_from_slice(&buf[.. len]);
+                }
+                assert_eq!(output, expected.as_bytes());
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn decode_reader() {
+    for &(base, input) in DECODE {
+        let expected = base.decode(input);
+        for size in 1 ..= input.len() + 1 {
+            for buf_len in 1 .. 10 {
+                let mut reader = base.new_async_decode_reader(Chunks::new(input, size));
+                let mut output = Vec::new();
+                let mut buf = vec![0; buf_len];
+                let result = loop {
+                    match reader.read(&mut buf).await {
+                        Ok(0) => break Ok(output),
+                        Ok(len) => output.extend_from_slice(&buf[.. len]),
+                        Err(error) => break Err(decode_error(error)),
+                    }
+                };
+                assert_eq!(result, expected);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn encode_writer() {
+    let wrapped = wrapped();
+    for &(base, input) in ENCODE.iter().chain(&[(&wrapped, &b"hello world"[..])]) {
+        let expected = base.encode(input);
+        for size in 1 ..= input.len() + 1 {
+            for write_size in 1 ..= input.len() + 1 {
+                let mut writer = base.new_async_encode_writer(Chunks::new(b"", write_size));
+                for chunk in input.chunks(size) {
+                    writer.write_all(chunk).await.unwrap();
+                }
+                writer.flush().await.unwrap();
+                writer.shutdown().await.unwrap();
+                assert_eq!(writer.into_inner().data, expected.as_bytes());
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn decode_writer() {
+    for &(base, input) in DECODE {
+        let expected = base.decode(input);
+        for size in 1 ..= input.len() + 1 {
+            for write_size in 1 .. 4 {
+                let mut writer = base.new_async_decode_writer(Chunks::new(b"", write_size));
+                let mut result = Ok(());
+                for chunk in input.chunks(size) {
+                    result = writer.write_all(chunk).await;
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                if result.is_ok() {
+                    result = writer.shutdown().await;
+                }
+                let result = result.map(|()| writer.into_inner().data).map_err(decode_error);
+                assert_eq!(result, expected);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn decode_writer_error() {
+    let base = &BASE64;
+    let mut writer = base.new_async_decode_writer(Chunks::new(b"", 1));
+    let error = writer.write(b"SGVsbG8gd29y!GQ=").await.unwrap_err();
+    assert_eq!(decode_error(error), DecodeError { position: 12, kind: DecodeKind::Symbol });
+    writer.flush().await.unwrap();
+    assert_eq!(writer.into_inner().data, b"");
+}
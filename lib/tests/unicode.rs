@@ -0,0 +1,145 @@
+//! Unicode tests
+
+use data_encoding::{DecodeError, DecodeKind, Encoding, Specification};
+
+// Maps ASCII characters to Braille patterns (which are 3 bytes in UTF-8).
+fn braille(c: char) -> char {
+    core::char::from_u32(0x2800 + c as u32).unwrap()
+}
+
+fn to_braille(s: &str) -> String {
+    s.chars().map(braille).collect()
+}
+
+fn braille_spec(spec: &Specification) -> Specification {
+    let mut spec = spec.clone();
+    spec.symbols = to_braille(&spec.symbols);
+    spec.padding = spec.padding.map(braille);
+    spec.ignore = to_braille(&spec.ignore);
+    spec.wrap.separator = to_braille(&spec.wrap.separator);
+    spec.translate.from = to_braille(&spec.translate.from);
+    spec.translate.to = to_braille(&spec.translate.to);
+    spec
+}
+
+#[track_caller]
+fn same(base: &Encoding) {
+    let spec = base.specification();
+    let unicode = spec.unicode_encoding().unwrap();
+    let other = braille_spec(&spec).unicode_encoding().unwrap();
+    assert_eq!(unicode.bit_width(), base.bit_width());
+    assert_eq!(to_braille(&unicode.specification().symbols), other.specification().symbols);
+    let data: Vec<u8> = (0 .. 40).map(|x: u8| x.wrapping_mul(97) ^ 0x5a).collect();
+    for len in 0 .. data.len() {
+        let encoded = base.encode(&data[.. len]);
+        assert_eq!(unicode.encode_len(len), base.encode_len(len));
+        assert_eq!(unicode.encode(&data[.. len]), encoded);
+        assert_eq!(other.encode(&data[.. len]), to_braille(&encoded));
+        assert_eq!(unicode.decode(&encoded).unwrap(), &data[.. len]);
+        assert_eq!(other.decode(&to_braille(&encoded)).unwrap(), &data[.. len]);
+        assert_eq!(unicode.decode_len(encoded.len()), base.decode_len(encoded.len()));
+        // Errors at the same position (in characters) as the ASCII encoding.
+        for i in 0 .. encoded.len() {
+            for &c in &['!', '~'] {
+                let mut input: Vec<char> = encoded.chars().collect();
+                input[i] = c;
+                let input: String = input.into_iter().collect();
+                let expected = base.decode(input.as_bytes());
+                assert_eq!(unicode.decode(&input), expected);
+                let expected = expected.map_err(|e| DecodeError { position: 3 * e.position, ..e });
+                let input: String =
+                    input.chars().map(|x| if x == c { x } else { braille(x) }).collect();
+                let actual = other.decode(&input).map_err(|e| DecodeError {
+                    position: input[.. e.position].chars().count() * 3,
+                    ..e
+                });
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn same_as_ascii() {
+    same(&data_encoding::HEXLOWER_PERMISSIVE);
+    same(&data_encoding::BASE32);
+    same(&data_encoding::BASE32_DNSCURVE);
+    same(&data_encoding::BASE64);
+    same(&data_encoding::BASE64URL_NOPAD);
+    same(&data_encoding::BASE64_MIME);
+    let mut spec = data_encoding::BASE64.specification();
+    spec.check_trailing_bits = false;
+    spec.ignore.push_str(" \t");
+    same(&spec.encoding().unwrap());
+    let mut spec = Specification::new();
+    spec.symbols.push_str("01234567");
+    spec.padding = Some('=');
+    spec.wrap.width = 8;
+    spec.wrap.separator.push_str(" |\n");
+    same(&spec.encoding().unwrap());
+}
+
+#[test]
+fn error_position() {
+    let mut spec = Specification::new();
+    spec.symbols.push_str("○●");
+    spec.ignore.push('·');
+    let binary = spec.unicode_encoding().unwrap();
+    assert_eq!(binary.decode("○●○●·●○●○·").unwrap(), [0x5a]);
+    let error = |position, kind| Err(DecodeError { position, kind });
+    assert_eq!(binary.decode("○●○●·●x●○"), error(17, DecodeKind::Symbol));
+    let mut spec = Specification::new();
+    spec.symbols.push_str("01");
+    spec.ignore.push(' ');
+    let ascii = spec.encoding().unwrap();
+    let length = ascii.decode(b"0101 010").unwrap_err();
+    assert_eq!(length.kind, DecodeKind::Length);
+    assert_eq!(binary.decode("○●○●·○●○"), Err(length));
+}
+
+#[test]
+fn specification_error() {
+    let error = |spec: &Specification| spec.unicode_encoding().unwrap_err().to_string();
+    let mut spec = Specification::new();
+    spec.symbols.push_str("○●◐");
+    assert_eq!(error(&spec), "invalid number of symbols");
+    spec.symbols.push('○');
+    assert_eq!(error(&spec), "'○' has conflicting definitions");
+    spec.symbols = "○●◐◑".to_string();
+    spec.padding = Some('◒');
+    assert_eq!(error(&spec), "unnecessary padding");
+    spec.symbols = "○●◐◑◒◓◔◕".to_string();
+    spec.padding = Some('◑');
+    assert_eq!(error(&spec), "'◑' has conflicting definitions");
+    spec.padding = Some('◖');
+    spec.translate.from.push('◗');
+    spec.translate.to.push('◘');
+    assert_eq!(error(&spec), "'◘' is undefined");
+    spec.translate.to = "◐".to_string();
+    assert!(spec.unicode_encoding().is_ok());
+    spec.ignore = (0x2800 .. 0x2880).map(|x| core::char::from_u32(x).unwrap()).collect();
+    assert_eq!(error(&spec), "too many distinct characters");
+}
+
+#[test]
+fn specification() {
+    let mut spec = Specification::new();
+    spec.symbols.push_str("🌑🌒🌓🌔🌕🌖🌗🌘");
+    spec.padding = Some('🌚');
+    spec.ignore.push('🌙');
+    spec.wrap.width = 8;
+    spec.wrap.separator.push('🌟');
+    spec.translate.from.push('🌝');
+    spec.translate.to.push('🌕');
+    let moon = spec.unicode_encoding().unwrap();
+    let actual = moon.specification();
+    assert_eq!(actual.symbols, spec.symbols);
+    assert_eq!(actual.padding, spec.padding);
+    assert_eq!(actual.wrap.width, 8);
+    assert_eq!(actual.wrap.separator, "🌟");
+    assert_eq!(actual.translate.from, "🌝");
+    assert_eq!(actual.translate.to, "🌕");
+    assert_eq!(moon, actual.unicode_encoding().unwrap());
+    assert_eq!(moon.encode(b"Hello"), "🌓🌓🌑🌗🌓🌖🌖🌕🌟🌔🌔🌑🌗🌘🌕🌚🌚🌟");
+    assert_eq!(moon.decode("🌓🌓🌑🌗🌓🌖🌖🌝🌙🌔🌔🌑🌗🌘🌕🌚🌚").unwrap(), b"Hello");
+}